@@ -16,6 +16,7 @@ replace (
 )
 
 require (
+	cosmossdk.io/errors v1.0.1
 	cosmossdk.io/math v1.5.0
 	github.com/CoreumFoundation/coreum/v5 v5.0.0-20250414180032-219788281a9a
 	github.com/CosmWasm/wasmd v0.54.0
@@ -33,7 +34,6 @@ require (
 	cosmossdk.io/api v0.7.6 // indirect
 	cosmossdk.io/client/v2 v2.0.0-beta.3 // indirect
 	cosmossdk.io/collections v0.4.0 // indirect
-	cosmossdk.io/errors v1.0.1 // indirect
 	cosmossdk.io/store v1.1.1 // indirect
 	cosmossdk.io/x/evidence v0.1.1 // indirect
 	cosmossdk.io/x/feegrant v0.1.1 // indirect
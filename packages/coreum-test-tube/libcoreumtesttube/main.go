@@ -4,19 +4,26 @@ import "C"
 
 import (
 	"encoding/base64"
+	"encoding/binary"
 	"encoding/json"
 	"fmt"
 	"os"
+	"strings"
 	"sync"
+	"sync/atomic"
 	"time"
 
 	wasmtypes "github.com/CosmWasm/wasmd/x/wasm/types"
 	abci "github.com/cometbft/cometbft/abci/types"
 	tmproto "github.com/cometbft/cometbft/proto/tendermint/types"
+	dbm "github.com/cosmos/cosmos-db"
 	codectypes "github.com/cosmos/cosmos-sdk/codec/types"
 	"github.com/cosmos/cosmos-sdk/crypto/keys/secp256k1"
 	sdk "github.com/cosmos/cosmos-sdk/types"
+	signingtypes "github.com/cosmos/cosmos-sdk/types/tx/signing"
+	authsigning "github.com/cosmos/cosmos-sdk/x/auth/signing"
 	"github.com/cosmos/cosmos-sdk/x/bank/testutil"
+	genutiltypes "github.com/cosmos/cosmos-sdk/x/genutil/types"
 	slashingtypes "github.com/cosmos/cosmos-sdk/x/slashing/types"
 	"github.com/cosmos/gogoproto/proto"
 	"github.com/pkg/errors"
@@ -26,73 +33,139 @@ import (
 )
 
 var (
-	envCounter  uint64 = 0
-	envRegister        = sync.Map{}
-	mu          sync.Mutex
+	envCounter      atomic.Uint64
+	snapshotCounter atomic.Uint64
+
+	envRegister      = sync.Map{}
+	snapshotRegister = sync.Map{}
+
+	// initMu serializes the creation of the apps, which sets process wide state
+	// such as the address prefixes and the max wasm size.
+	initMu sync.Mutex
 )
 
+// registeredEnv is an env with the lock serializing the calls on it,
+// the calls on different envs run in parallel.
+type registeredEnv struct {
+	mu  sync.Mutex
+	env *testenv.TestEnv
+}
+
+// registeredSnapshot is a snapshot with the id of the env it was taken from,
+// which is the only env it can be restored to.
+type registeredSnapshot struct {
+	envId    uint64
+	snapshot *testenv.Snapshot
+}
+
 //export InitTestEnv
 func InitTestEnv() uint64 {
-	// Temp fix for concurrency issue
-	mu.Lock()
-	defer mu.Unlock()
+	initMu.Lock()
+	defer initMu.Unlock()
 
-	nodeHome, err := os.MkdirTemp("", ".coreum-test-tube-temp-")
+	id, err := initTestEnv(testenv.DefaultConfig())
 	if err != nil {
 		panic(err)
 	}
 
-	// set up the validator
-	env := new(testenv.TestEnv)
-	env.App, env.Validator = testenv.SetupApp(nodeHome)
-	env.ParamTypesRegistry = *testenv.NewParamTypeRegistry()
+	return id
+}
 
-	env.SetupParamTypes()
+//export InitTestEnvWithConfig
+func InitTestEnvWithConfig(configJson string) *C.char {
+	initMu.Lock()
+	defer initMu.Unlock()
 
-	// Allow testing unoptimized contract
-	wasmtypes.MaxWasmSize = 1024 * 1024 * 1024 * 1024 * 1024
+	cfg, err := testenv.ParseConfig(configJson)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
 
-	env.Ctx = env.App.BaseApp.NewContextLegacy(false, tmproto.Header{Height: 0, ChainID: string(testenv.NetworkConfig.ChainID()), Time: time.Now().UTC()})
+	id, err := initTestEnv(cfg)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
 
-	validators, err := env.App.StakingKeeper.GetAllValidators(env.Ctx)
+	return encodeBytesResultBytes(binary.BigEndian.AppendUint64(nil, id))
+}
+
+//export InitTestEnvFromGenesis
+func InitTestEnvFromGenesis(genesisJson, addressPrefix, denom string) *C.char {
+	initMu.Lock()
+	defer initMu.Unlock()
+
+	appGenesis, err := genutiltypes.AppGenesisFromReader(strings.NewReader(genesisJson))
 	if err != nil {
-		panic(err)
+		return encodeErrToResultBytes(result.ExecuteError, err)
 	}
-	valAddrFancy, _ := validators[0].GetConsAddr()
-	err = env.App.SlashingKeeper.SetValidatorSigningInfo(env.Ctx, valAddrFancy, slashingtypes.NewValidatorSigningInfo(
-		valAddrFancy,
-		0,
-		0,
-		time.Unix(0, 0),
-		false,
-		0,
-	))
+	if err := appGenesis.ValidateAndComplete(); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	nodeHome, err := os.MkdirTemp("", ".coreum-test-tube-temp-")
 	if err != nil {
 		panic(err)
 	}
 
-	env.BeginNewBlock(5)
-	reqFinalizeBlock := &abci.RequestFinalizeBlock{Height: env.Ctx.BlockHeight()}
-	_, err = env.App.FinalizeBlock(reqFinalizeBlock)
+	env := new(testenv.TestEnv)
+	env.DB = dbm.NewMemDB()
+	env.NodeHome = nodeHome
+	env.Config = testenv.DefaultConfig()
+	env.Config.ChainID = appGenesis.ChainID
+	env.Config.AddressPrefix = addressPrefix
+	env.Config.Denom = denom
+	env.App, err = testenv.SetupAppWithGenesis(nodeHome, env.DB, env.Config, appGenesis)
 	if err != nil {
-		panic(err)
+		return encodeErrToResultBytes(result.ExecuteError, err)
 	}
-	_, err = env.App.Commit()
+
+	id, err := startTestEnv(env, appGenesis.InitialHeight-1, appGenesis.GenesisTime.UTC())
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	return encodeBytesResultBytes(binary.BigEndian.AppendUint64(nil, id))
+}
+
+//export ForkTestEnv
+func ForkTestEnv(envId uint64) uint64 {
+	env, unlock := lockEnv(envId)
+	defer unlock()
+
+	initMu.Lock()
+	defer initMu.Unlock()
+
+	fork, err := env.Fork()
 	if err != nil {
 		panic(err)
 	}
 
-	envCounter += 1
-	id := envCounter
+	return registerEnv(fork)
+}
 
-	envRegister.Store(id, *env)
+//export ExportGenesis
+func ExportGenesis(envId uint64) *C.char {
+	env, unlock := lockEnv(envId)
+	defer unlock()
 
-	return id
+	appGenesis, err := env.ExportGenesis()
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	bz, err := appGenesis.ToJSON()
+	if err != nil {
+		panic(err)
+	}
+
+	return encodeBytesResultBytes(bz)
 }
 
 //export CleanUp
 func CleanUp(envId uint64) {
-	env := loadEnv(envId)
+	env, unlock := lockEnv(envId)
+	defer unlock()
+
 	if env.NodeHome == "" {
 		return
 	}
@@ -105,7 +178,8 @@ func CleanUp(envId uint64) {
 
 //export InitAccount
 func InitAccount(envId uint64, coinsJson string) *C.char {
-	env := loadEnv(envId)
+	env, unlock := lockEnv(envId)
+	defer unlock()
 	var coins sdk.Coins
 
 	if err := json.Unmarshal([]byte(coinsJson), &coins); err != nil {
@@ -122,47 +196,107 @@ func InitAccount(envId uint64, coinsJson string) *C.char {
 
 	base64Priv := base64.StdEncoding.EncodeToString(priv.Bytes())
 
-	envRegister.Store(envId, env)
-
 	return C.CString(base64Priv)
 }
 
+//export FundAccount
+func FundAccount(envId uint64, bech32Address, coinsJson string) *C.char {
+	env, unlock := lockEnv(envId)
+	defer unlock()
+	var coins sdk.Coins
+
+	if err := json.Unmarshal([]byte(coinsJson), &coins); err != nil {
+		panic(err)
+	}
+
+	addr, err := sdk.AccAddressFromBech32(bech32Address)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	err = testutil.FundAccount(env.Ctx, env.App.BankKeeper, addr, coins)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, errors.Wrapf(err, "Failed to fund account"))
+	}
+
+	// return empty bytes if no error
+	return encodeBytesResultBytes([]byte{})
+}
+
 //export IncreaseTime
-func IncreaseTime(envId, seconds uint64) {
-	env := loadEnv(envId)
+func IncreaseTime(envId, seconds uint64) *C.char { // => base64ResponseFinalizeBlock
+	env, unlock := lockEnv(envId)
+	defer unlock()
 	env.BeginNewBlock(seconds)
-	envRegister.Store(envId, env)
-	EndBlock(envId)
+	res := env.EndBlock()
+
+	return encodeFinalizeBlockResultBytes(res)
+}
+
+//export SetBlockTime
+func SetBlockTime(envId uint64, nanos int64) *C.char {
+	env, unlock := lockEnv(envId)
+	defer unlock()
+
+	blockTime := time.Unix(0, nanos).UTC()
+	if !blockTime.After(env.Ctx.BlockTime()) {
+		err := errors.Errorf("block time %s must be after the current block time %s", blockTime, env.Ctx.BlockTime())
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	env.BeginNewBlockWithTimeIncrease(blockTime.Sub(env.Ctx.BlockTime()))
+	res := env.EndBlock()
+
+	return encodeFinalizeBlockResultBytes(res)
+}
+
+//export AdvanceBlocks
+func AdvanceBlocks(envId, blocks, secondsPerBlock uint64) *C.char { // => base64ResponseFinalizeBlock
+	env, unlock := lockEnv(envId)
+	defer unlock()
+	res := env.AdvanceBlocks(blocks, time.Duration(secondsPerBlock)*time.Second)
+
+	return encodeFinalizeBlockResultBytes(res)
+}
+
+//export AdvanceToHeight
+func AdvanceToHeight(envId uint64, height int64, secondsPerBlock uint64) *C.char {
+	env, unlock := lockEnv(envId)
+	defer unlock()
+
+	if height <= env.Ctx.BlockHeight() {
+		err := errors.Errorf("height %d must be greater than the current height %d", height, env.Ctx.BlockHeight())
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	res := env.AdvanceBlocks(uint64(height-env.Ctx.BlockHeight()), time.Duration(secondsPerBlock)*time.Second)
+
+	return encodeFinalizeBlockResultBytes(res)
 }
 
 //export BeginBlock
 func BeginBlock(envId uint64) {
-	env := loadEnv(envId)
+	env, unlock := lockEnv(envId)
+	defer unlock()
 	env.BeginNewBlock(1)
-	envRegister.Store(envId, env)
 }
 
 //export EndBlock
 func EndBlock(envId uint64) {
-	env := loadEnv(envId)
-	reqFinalizeBlock := &abci.RequestFinalizeBlock{Height: env.Ctx.BlockHeight(), Time: env.Ctx.BlockTime()}
-	_, err := env.App.FinalizeBlock(reqFinalizeBlock)
-	if err != nil {
-		panic(err)
-	}
-	_, err = env.App.Commit()
-	if err != nil {
-		panic(err)
-	}
-	envRegister.Store(envId, env)
+	endBlock(envId)
+}
+
+//export EndBlockWithResult
+func EndBlockWithResult(envId uint64) *C.char {
+	res := endBlock(envId)
+
+	return encodeFinalizeBlockResultBytes(res)
 }
 
 //export Execute
 func Execute(envId uint64, base64Tx string) *C.char {
-	env := loadEnv(envId)
-	// Temp fix for concurrency issue
-	mu.Lock()
-	defer mu.Unlock()
+	env, unlock := lockEnv(envId)
+	defer unlock()
 
 	txBytes, err := base64.StdEncoding.DecodeString(base64Tx)
 	if err != nil {
@@ -172,8 +306,10 @@ func Execute(envId uint64, base64Tx string) *C.char {
 	gasInfo, resDeliverTx, err := env.App.SimDeliver(func(tx sdk.Tx) ([]byte, error) {
 		return txBytes, nil
 	}, nil)
+	env.IndexTx(txBytes, gasInfo, resDeliverTx, err)
 	if err != nil {
-		panic(err)
+		// failed transactions, e.g. malformed or replayed ones, are reported instead of aborting
+		return encodeTxErrToResultBytes(err, gasInfo.GasUsed)
 	}
 	res := sdk.SimulationResponse{
 		GasInfo: gasInfo,
@@ -184,14 +320,39 @@ func Execute(envId uint64, base64Tx string) *C.char {
 		panic(err)
 	}
 
-	envRegister.Store(envId, env)
+	return encodeBytesResultBytes(bz)
+}
+
+//export CheckTx
+func CheckTx(envId uint64, base64Tx string) *C.char { // => base64ResponseCheckTx
+	env, unlock := lockEnv(envId)
+	defer unlock()
+
+	txBytes, err := base64.StdEncoding.DecodeString(base64Tx)
+	if err != nil {
+		panic(err)
+	}
+
+	res, err := env.App.CheckTx(&abci.RequestCheckTx{
+		Tx:   txBytes,
+		Type: abci.CheckTxType_New,
+	})
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	bz, err := proto.Marshal(res)
+	if err != nil {
+		panic(err)
+	}
 
 	return encodeBytesResultBytes(bz)
 }
 
 //export Query
 func Query(envId uint64, path, base64QueryMsgBytes string) *C.char {
-	env := loadEnv(envId)
+	env, unlock := lockEnv(envId)
+	defer unlock()
 	queryMsgBytes, err := base64.StdEncoding.DecodeString(base64QueryMsgBytes)
 	if err != nil {
 		panic(err)
@@ -213,54 +374,151 @@ func Query(envId uint64, path, base64QueryMsgBytes string) *C.char {
 	return encodeBytesResultBytes(res.Value)
 }
 
+//export QueryTx
+func QueryTx(envId uint64, hash string) *C.char { // => base64TxResponse
+	env, unlock := lockEnv(envId)
+	defer unlock()
+
+	txRes, ok := env.FindTx(hash)
+	if !ok {
+		return encodeErrToResultBytes(result.QueryError, errors.Errorf("tx %s not found", hash))
+	}
+
+	bz, err := proto.Marshal(txRes)
+	if err != nil {
+		panic(err)
+	}
+
+	return encodeBytesResultBytes(bz)
+}
+
+//export SearchTxs
+func SearchTxs(envId uint64, query string) *C.char { // => base64SearchTxsResult
+	env, unlock := lockEnv(envId)
+	defer unlock()
+
+	txs, err := env.SearchTxs(query)
+	if err != nil {
+		return encodeErrToResultBytes(result.QueryError, err)
+	}
+
+	count := uint64(len(txs))
+	bz, err := proto.Marshal(&sdk.SearchTxsResult{
+		TotalCount: count,
+		Count:      count,
+		PageNumber: 1,
+		PageTotal:  1,
+		Limit:      count,
+		Txs:        txs,
+	})
+	if err != nil {
+		panic(err)
+	}
+
+	return encodeBytesResultBytes(bz)
+}
+
 //export GetBlockTime
 func GetBlockTime(envId uint64) int64 {
-	env := loadEnv(envId)
+	env, unlock := lockEnv(envId)
+	defer unlock()
 	return env.Ctx.BlockTime().UnixNano()
 }
 
 //export GetBlockHeight
 func GetBlockHeight(envId uint64) int64 {
-	env := loadEnv(envId)
+	env, unlock := lockEnv(envId)
+	defer unlock()
 	return env.Ctx.BlockHeight()
 }
 
 //export AccountSequence
-func AccountSequence(envId uint64, bech32Address string) uint64 {
-	env := loadEnv(envId)
+func AccountSequence(envId uint64, bech32Address string) *C.char {
+	env, unlock := lockEnv(envId)
+	defer unlock()
 
 	addr, err := sdk.AccAddressFromBech32(bech32Address)
 	if err != nil {
-		panic(err)
+		return encodeErrToResultBytes(result.QueryError, err)
 	}
 
 	seq, err := env.App.AccountKeeper.GetSequence(env.Ctx, addr)
 	if err != nil {
-		panic(err)
+		return encodeErrToResultBytes(result.QueryError, err)
 	}
 
-	return seq
+	return encodeBytesResultBytes(binary.BigEndian.AppendUint64(nil, seq))
 }
 
 //export AccountNumber
-func AccountNumber(envId uint64, bech32Address string) uint64 {
-	env := loadEnv(envId)
+func AccountNumber(envId uint64, bech32Address string) *C.char {
+	env, unlock := lockEnv(envId)
+	defer unlock()
 
 	addr, err := sdk.AccAddressFromBech32(bech32Address)
 	if err != nil {
-		panic(err)
+		return encodeErrToResultBytes(result.QueryError, err)
 	}
 
 	acc := env.App.AccountKeeper.GetAccount(env.Ctx, addr)
-	return acc.GetAccountNumber()
+	if acc == nil {
+		return encodeErrToResultBytes(result.QueryError, fmt.Errorf("account %s not found", bech32Address))
+	}
+
+	return encodeBytesResultBytes(binary.BigEndian.AppendUint64(nil, acc.GetAccountNumber()))
+}
+
+//export GetSignBytes
+func GetSignBytes(envId uint64, signMode int32, base64TxBytes string, signerIndex int, accountNumber, sequence uint64) *C.char { // => signBytes
+	env, unlock := lockEnv(envId)
+	defer unlock()
+
+	txBytes, err := base64.StdEncoding.DecodeString(base64TxBytes)
+	if err != nil {
+		panic(err)
+	}
+
+	txConfig := env.App.TxConfig()
+	tx, err := txConfig.TxDecoder()(txBytes)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, errors.Wrap(err, "Failed to decode tx"))
+	}
+
+	pubKeys, err := tx.(authsigning.Tx).GetPubKeys()
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, errors.Wrap(err, "Failed to get tx public keys"))
+	}
+	if signerIndex < 0 || signerIndex >= len(pubKeys) {
+		return encodeErrToResultBytes(result.ExecuteError, fmt.Errorf("signer index %d out of range", signerIndex))
+	}
+
+	// the account number and sequence are the ones signed by the caller, which may differ from the
+	// ones of the account on chain
+	pubKey := pubKeys[signerIndex]
+	signBytes, err := authsigning.GetSignBytesAdapter(
+		env.Ctx,
+		txConfig.SignModeHandler(),
+		signingtypes.SignMode(signMode),
+		authsigning.SignerData{
+			Address:       sdk.AccAddress(pubKey.Address()).String(),
+			ChainID:       env.Ctx.ChainID(),
+			AccountNumber: accountNumber,
+			Sequence:      sequence,
+			PubKey:        pubKey,
+		},
+		tx,
+	)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, errors.Wrap(err, "Failed to get sign bytes"))
+	}
+
+	return encodeBytesResultBytes(signBytes)
 }
 
 //export Simulate
 func Simulate(envId uint64, base64TxBytes string) *C.char { // => base64GasInfo
-	env := loadEnv(envId)
-	// Temp fix for concurrency issue
-	mu.Lock()
-	defer mu.Unlock()
+	env, unlock := lockEnv(envId)
+	defer unlock()
 
 	txBytes, err := base64.StdEncoding.DecodeString(base64TxBytes)
 	if err != nil {
@@ -269,7 +527,7 @@ func Simulate(envId uint64, base64TxBytes string) *C.char { // => base64GasInfo
 
 	gasInfo, _, err := env.App.Simulate(txBytes)
 	if err != nil {
-		return encodeErrToResultBytes(result.ExecuteError, err)
+		return encodeTxErrToResultBytes(err, gasInfo.GasUsed)
 	}
 
 	bz, err := proto.Marshal(&gasInfo)
@@ -282,11 +540,8 @@ func Simulate(envId uint64, base64TxBytes string) *C.char { // => base64GasInfo
 
 //export SetParamSet
 func SetParamSet(envId uint64, subspaceName, base64ParamSetBytes string) *C.char {
-	env := loadEnv(envId)
-
-	// Temp fix for concurrency issue
-	mu.Lock()
-	defer mu.Unlock()
+	env, unlock := lockEnv(envId)
+	defer unlock()
 
 	paramSetBytes, err := base64.StdEncoding.DecodeString(base64ParamSetBytes)
 	if err != nil {
@@ -321,7 +576,8 @@ func SetParamSet(envId uint64, subspaceName, base64ParamSetBytes string) *C.char
 
 //export GetParamSet
 func GetParamSet(envId uint64, subspaceName, typeUrl string) *C.char {
-	env := loadEnv(envId)
+	env, unlock := lockEnv(envId)
+	defer unlock()
 
 	subspace, ok := env.App.ParamsKeeper.GetSubspace(subspaceName)
 	if !ok {
@@ -347,38 +603,195 @@ func GetParamSet(envId uint64, subspaceName, typeUrl string) *C.char {
 
 //export GetValidatorAddress
 func GetValidatorAddress(envId uint64, n int32) *C.char {
-	env := loadEnv(envId)
+	env, unlock := lockEnv(envId)
+	defer unlock()
 	return C.CString(env.GetValidatorAddresses()[n])
 }
 
 //export GetValidatorPrivateKey
 func GetValidatorPrivateKey(envId uint64) *C.char {
-	env := loadEnv(envId)
+	env, unlock := lockEnv(envId)
+	defer unlock()
 	priv := env.GetValidatorPrivateKey()
 	base64Priv := base64.StdEncoding.EncodeToString(priv)
 
 	return C.CString(base64Priv)
 }
 
+//export TakeSnapshot
+func TakeSnapshot(envId uint64) uint64 {
+	env, unlock := lockEnv(envId)
+	defer unlock()
+
+	id := snapshotCounter.Add(1)
+
+	snapshotRegister.Store(id, &registeredSnapshot{envId: envId, snapshot: env.Snapshot()})
+
+	return id
+}
+
+//export RestoreSnapshot
+func RestoreSnapshot(envId, snapshotId uint64) *C.char {
+	env, unlock := lockEnv(envId)
+	defer unlock()
+
+	item, ok := snapshotRegister.Load(snapshotId)
+	if !ok {
+		err := errors.Errorf("snapshot not found, snapshotId:%d", snapshotId)
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+	registered := item.(*registeredSnapshot)
+	if registered.envId != envId {
+		err := errors.Errorf("snapshot %d was taken from env %d, not env %d", snapshotId, registered.envId, envId)
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	initMu.Lock()
+	defer initMu.Unlock()
+
+	if err := env.Restore(registered.snapshot); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	// return empty bytes if no error
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export ReleaseSnapshot
+func ReleaseSnapshot(snapshotId uint64) {
+	snapshotRegister.Delete(snapshotId)
+}
+
 // ========= utils =========
 
-func loadEnv(envId uint64) testenv.TestEnv {
+// endBlock finalizes and commits the current block of the env.
+func endBlock(envId uint64) *abci.ResponseFinalizeBlock {
+	env, unlock := lockEnv(envId)
+	defer unlock()
+	res := env.EndBlock()
+
+	return res
+}
+
+func initTestEnv(cfg testenv.Config) (uint64, error) {
+	nodeHome, err := os.MkdirTemp("", ".coreum-test-tube-temp-")
+	if err != nil {
+		panic(err)
+	}
+
+	// set up the validator
+	env := new(testenv.TestEnv)
+	env.DB = dbm.NewMemDB()
+	env.NodeHome = nodeHome
+	env.Config = cfg
+	env.App, env.Validator, err = testenv.SetupApp(nodeHome, env.DB, cfg)
+	if err != nil {
+		return 0, err
+	}
+
+	return startTestEnv(env, 0, time.Now().UTC())
+}
+
+// startTestEnv finalizes the first block of the env on top of the given height and registers it.
+func startTestEnv(env *testenv.TestEnv, height int64, blockTime time.Time) (uint64, error) {
+	env.ParamTypesRegistry = *testenv.NewParamTypeRegistry()
+
+	env.SetupParamTypes()
+
+	// Allow testing unoptimized contract
+	wasmtypes.MaxWasmSize = 1024 * 1024 * 1024 * 1024 * 1024
+
+	env.Ctx = env.App.BaseApp.NewContextLegacy(false, tmproto.Header{Height: height, ChainID: env.Config.ChainID, Time: blockTime})
+
+	validators, err := env.App.StakingKeeper.GetAllValidators(env.Ctx)
+	if err != nil {
+		return 0, errors.Wrap(err, "failed to get validators")
+	}
+	if len(validators) == 0 {
+		return 0, errors.New("genesis has no validators")
+	}
+	valAddrFancy, err := validators[0].GetConsAddr()
+	if err != nil {
+		return 0, errors.Wrap(err, "failed to get validator consensus address")
+	}
+	err = env.App.SlashingKeeper.SetValidatorSigningInfo(env.Ctx, valAddrFancy, slashingtypes.NewValidatorSigningInfo(
+		valAddrFancy,
+		0,
+		0,
+		time.Unix(0, 0),
+		false,
+		0,
+	))
+	if err != nil {
+		panic(err)
+	}
+
+	for _, balance := range env.Config.Balances {
+		addr, err := sdk.AccAddressFromBech32(balance.Address)
+		if err != nil {
+			return 0, errors.Wrapf(err, "invalid balance address `%s`", balance.Address)
+		}
+		if err := testutil.FundAccount(env.Ctx, env.App.BankKeeper, addr, balance.Coins); err != nil {
+			return 0, errors.Wrapf(err, "failed to fund `%s`", balance.Address)
+		}
+	}
+
+	env.BeginNewBlock(5)
+	reqFinalizeBlock := &abci.RequestFinalizeBlock{Height: env.Ctx.BlockHeight()}
+	_, err = env.App.FinalizeBlock(reqFinalizeBlock)
+	if err != nil {
+		panic(err)
+	}
+	_, err = env.App.Commit()
+	if err != nil {
+		panic(err)
+	}
+
+	return registerEnv(env), nil
+}
+
+// registerEnv stores the env under a new id.
+func registerEnv(env *testenv.TestEnv) uint64 {
+	id := envCounter.Add(1)
+	envRegister.Store(id, &registeredEnv{env: env})
+
+	return id
+}
+
+// lockEnv locks the env until the returned unlock function is called. The lock covers a single
+// call, callers producing a block with BeginBlock, Execute and EndBlock hold their own lock
+// across the calls.
+func lockEnv(envId uint64) (*testenv.TestEnv, func()) {
 	item, ok := envRegister.Load(envId)
 	if !ok {
 		panic(fmt.Sprintf("env not found, envId:%d", envId))
 	}
-	env, ok := item.(testenv.TestEnv)
+	registered, ok := item.(*registeredEnv)
 	if !ok {
-		panic(fmt.Sprintf("failed to cast env to testenv.TestEnv, envId:%d", envId))
+		panic(fmt.Sprintf("failed to cast env to registeredEnv, envId:%d", envId))
 	}
+	registered.mu.Lock()
 
-	return env
+	return registered.env, registered.mu.Unlock
 }
 
 func encodeErrToResultBytes(code byte, err error) *C.char {
 	return C.CString(result.EncodeResultFromError(code, err))
 }
 
+func encodeTxErrToResultBytes(err error, gasUsed uint64) *C.char {
+	return C.CString(result.EncodeResultFromTxError(err, gasUsed))
+}
+
+func encodeFinalizeBlockResultBytes(res *abci.ResponseFinalizeBlock) *C.char {
+	bz, err := proto.Marshal(res)
+	if err != nil {
+		panic(err)
+	}
+
+	return encodeBytesResultBytes(bz)
+}
+
 func encodeBytesResultBytes(bytes []byte) *C.char {
 	return C.CString(result.EncodeResultFromOk(bytes))
 }
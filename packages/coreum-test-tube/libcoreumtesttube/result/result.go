@@ -3,14 +3,26 @@ package result
 import (
 	"C"
 	"encoding/base64"
+	"encoding/json"
+
+	errorsmod "cosmossdk.io/errors"
 )
 
 var (
-	Ok           byte = 0
-	QueryError   byte = 1
-	ExecuteError byte = 2
+	Ok             byte = 0
+	QueryError     byte = 1
+	ExecuteError   byte = 2
+	ExecuteTxError byte = 3
 )
 
+// TxError is the ABCI representation of a failed transaction.
+type TxError struct {
+	Code      uint32 `json:"code"`
+	Codespace string `json:"codespace"`
+	Log       string `json:"log"`
+	GasUsed   uint64 `json:"gas_used"`
+}
+
 func markError(code byte, data []byte) []byte {
 	return append([]byte{code}, data...)
 }
@@ -24,6 +36,22 @@ func EncodeResultFromError(code byte, err error) string {
 	return base64.StdEncoding.EncodeToString(marked)
 }
 
+func EncodeResultFromTxError(err error, gasUsed uint64) string {
+	codespace, code, log := errorsmod.ABCIInfo(err, false)
+	bz, err := json.Marshal(TxError{
+		Code:      code,
+		Codespace: codespace,
+		Log:       log,
+		GasUsed:   gasUsed,
+	})
+	if err != nil {
+		panic(err)
+	}
+
+	marked := markError(ExecuteTxError, bz)
+	return base64.StdEncoding.EncodeToString(marked)
+}
+
 func EncodeResultFromOk(data []byte) string {
 	marked := markOk(data)
 	return base64.StdEncoding.EncodeToString(marked)
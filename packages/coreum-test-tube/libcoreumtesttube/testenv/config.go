@@ -0,0 +1,147 @@
+package testenv
+
+import (
+	"encoding/json"
+	"sync"
+	"time"
+
+	"cosmossdk.io/math"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	"github.com/pkg/errors"
+
+	coreumconfig "github.com/CoreumFoundation/coreum/v5/pkg/config"
+	coreumconstant "github.com/CoreumFoundation/coreum/v5/pkg/config/constant"
+)
+
+// Config defines the network and the genesis the env is created with.
+type Config struct {
+	ChainID                string                     `json:"chain_id"`
+	AddressPrefix          string                     `json:"address_prefix"`
+	Denom                  string                     `json:"denom"`
+	GovMinDeposit          math.Int                   `json:"gov_min_deposit"`
+	GovVotingPeriodSeconds uint64                     `json:"gov_voting_period_seconds"`
+	MinSelfDelegation      math.Int                   `json:"min_self_delegation"`
+	ModuleGenesis          map[string]json.RawMessage `json:"module_genesis"`
+	Balances               []Balance                  `json:"balances"`
+}
+
+// Balance is an amount of coins minted to an address at the start of the env.
+type Balance struct {
+	Address string    `json:"address"`
+	Coins   sdk.Coins `json:"coins"`
+}
+
+// DefaultConfig returns the mainnet-like config used when nothing is overridden.
+func DefaultConfig() Config {
+	return Config{
+		ChainID:                string(coreumconstant.ChainIDMain),
+		AddressPrefix:          coreumconstant.AddressPrefixMain,
+		Denom:                  coreumconstant.DenomMain,
+		GovMinDeposit:          math.NewInt(1000),
+		GovVotingPeriodSeconds: 10,
+		MinSelfDelegation:      math.NewInt(10_000_000), // 10 core
+	}
+}
+
+// ParseConfig decodes the config JSON on top of the default config, so only overridden fields need to be set.
+func ParseConfig(configJson string) (Config, error) {
+	cfg := DefaultConfig()
+	if err := json.Unmarshal([]byte(configJson), &cfg); err != nil {
+		return Config{}, errors.Wrap(err, "invalid env config")
+	}
+
+	return cfg, nil
+}
+
+func newNetworkConfig(cfg Config) coreumconfig.NetworkConfig {
+	networkConfig := coreumconfig.NetworkConfig{
+		Provider: coreumconfig.DynamicConfigProvider{
+			GenesisInitConfig: coreumconfig.GenesisInitConfig{
+				AddressPrefix: cfg.AddressPrefix,
+				ChainID:       coreumconstant.ChainID(cfg.ChainID),
+				GenesisTime:   time.Now(),
+				Denom:         cfg.Denom,
+				GovConfig: coreumconfig.GenesisInitGovConfig{
+					MinDeposit:   sdk.Coins{sdk.NewCoin(cfg.Denom, cfg.GovMinDeposit)},
+					VotingPeriod: time.Second * time.Duration(cfg.GovVotingPeriodSeconds),
+				},
+				CustomParamsConfig: coreumconfig.GenesisInitCustomParamsConfig{
+					MinSelfDelegation: cfg.MinSelfDelegation,
+				},
+			},
+		},
+	}
+
+	return networkConfig
+}
+
+// sdkConfigPrefix is the address prefix the global SDK config is sealed with, guarded by sdkConfigMu.
+var (
+	sdkConfigMu     sync.Mutex
+	sdkConfigPrefix string
+)
+
+// setSDKConfig sets and seals the global SDK config for the address prefix on the first call. The config is
+// process wide, so the envs of the process must all use the same prefix, any other one is rejected.
+func setSDKConfig(addressPrefix string) error {
+	sdkConfigMu.Lock()
+	defer sdkConfigMu.Unlock()
+
+	if sdkConfigPrefix != "" {
+		if addressPrefix != sdkConfigPrefix {
+			return errors.Errorf(
+				"address prefix `%s` differs from `%s` used by the other envs, only one prefix is supported per process",
+				addressPrefix, sdkConfigPrefix,
+			)
+		}
+		return nil
+	}
+
+	config := sdk.GetConfig()
+	config.SetBech32PrefixForAccount(addressPrefix, addressPrefix+sdk.PrefixPublic)
+	config.SetBech32PrefixForValidator(
+		addressPrefix+sdk.PrefixValidator+sdk.PrefixOperator,
+		addressPrefix+sdk.PrefixValidator+sdk.PrefixOperator+sdk.PrefixPublic,
+	)
+	config.SetBech32PrefixForConsensusNode(
+		addressPrefix+sdk.PrefixValidator+sdk.PrefixConsensus,
+		addressPrefix+sdk.PrefixValidator+sdk.PrefixConsensus+sdk.PrefixPublic,
+	)
+	config.SetCoinType(coreumconstant.CoinType)
+	config.Seal()
+	sdkConfigPrefix = addressPrefix
+
+	return nil
+}
+
+// mergeJSON deep merges the override into the original object, values of the override take precedence.
+func mergeJSON(original, override json.RawMessage) (json.RawMessage, error) {
+	var originalValue, overrideValue any
+	if len(original) > 0 {
+		if err := json.Unmarshal(original, &originalValue); err != nil {
+			return nil, err
+		}
+	}
+	if err := json.Unmarshal(override, &overrideValue); err != nil {
+		return nil, err
+	}
+
+	return json.Marshal(mergeValues(originalValue, overrideValue))
+}
+
+func mergeValues(original, override any) any {
+	originalMap, ok := original.(map[string]any)
+	if !ok {
+		return override
+	}
+	overrideMap, ok := override.(map[string]any)
+	if !ok {
+		return override
+	}
+
+	for key, value := range overrideMap {
+		originalMap[key] = mergeValues(originalMap[key], value)
+	}
+
+	return originalMap
+}
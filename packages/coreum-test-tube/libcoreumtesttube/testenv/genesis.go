@@ -0,0 +1,50 @@
+package testenv
+
+import (
+	abci "github.com/cometbft/cometbft/abci/types"
+	dbm "github.com/cosmos/cosmos-db"
+	genutiltypes "github.com/cosmos/cosmos-sdk/x/genutil/types"
+	"github.com/pkg/errors"
+
+	coreumapp "github.com/CoreumFoundation/coreum/v5/app"
+)
+
+// SetupAppWithGenesis initializes the app from the provided genesis instead of the generated network state.
+func SetupAppWithGenesis(nodeHome string, db dbm.DB, cfg Config, appGenesis *genutiltypes.AppGenesis) (*coreumapp.App, error) {
+	appInstance, err := NewApp(db, nodeHome, cfg)
+	if err != nil {
+		return nil, err
+	}
+
+	consensusParams := appGenesis.Consensus.Params.ToProto()
+	_, err = appInstance.InitChain(
+		&abci.RequestInitChain{
+			Time:            appGenesis.GenesisTime,
+			ChainId:         appGenesis.ChainID,
+			InitialHeight:   appGenesis.InitialHeight,
+			Validators:      []abci.ValidatorUpdate{},
+			ConsensusParams: &consensusParams,
+			AppStateBytes:   appGenesis.AppState,
+		},
+	)
+	if err != nil {
+		return nil, errors.Errorf("can't init chain: %s", err)
+	}
+
+	return appInstance, nil
+}
+
+// ExportGenesis exports the committed state of the env as a genesis, so it can be used to start a new env.
+func (env *TestEnv) ExportGenesis() (*genutiltypes.AppGenesis, error) {
+	exported, err := env.App.ExportAppStateAndValidators(false, nil, nil)
+	if err != nil {
+		return nil, errors.Wrap(err, "can't export app state")
+	}
+
+	appGenesis := genutiltypes.NewAppGenesisWithVersion(env.Config.ChainID, exported.AppState)
+	appGenesis.GenesisTime = env.Ctx.BlockTime()
+	appGenesis.InitialHeight = exported.Height
+	appGenesis.Consensus = genutiltypes.NewConsensusGenesis(exported.ConsensusParams, exported.Validators)
+
+	return appGenesis, nil
+}
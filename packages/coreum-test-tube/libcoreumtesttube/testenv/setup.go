@@ -30,25 +30,24 @@ import (
 
 	coreumapp "github.com/CoreumFoundation/coreum/v5/app"
 	coreumconfig "github.com/CoreumFoundation/coreum/v5/pkg/config"
-	coreumconstant "github.com/CoreumFoundation/coreum/v5/pkg/config/constant"
 	assetfttypes "github.com/CoreumFoundation/coreum/v5/x/asset/ft/types"
 	assetnfttypes "github.com/CoreumFoundation/coreum/v5/x/asset/nft/types"
 )
 
-var NetworkConfig coreumconfig.NetworkConfig
-
-func init() {
-	NetworkConfig = newNetworkConfig()
-	NetworkConfig.SetSDKConfig()
-	coreumapp.ChosenNetwork = NetworkConfig
-}
-
 type TestEnv struct {
 	App                *coreumapp.App
+	DB                 *dbm.MemDB
 	Ctx                sdk.Context
 	ParamTypesRegistry ParamTypeRegistry
 	Validator          []byte
 	NodeHome           string
+	Config             Config
+
+	// beginBlockResponse is the result of finalizing the current block when it began,
+	// merged into the result of ending the block.
+	beginBlockResponse *abci.ResponseFinalizeBlock
+	// txIndex holds the executed transactions, in the order they were executed.
+	txIndex []*sdk.TxResponse
 }
 
 // DebugAppOptions is a stub implementing AppOptions
@@ -62,18 +61,32 @@ func (ao DebugAppOptions) Get(o string) interface{} {
 	return nil
 }
 
-func SetupApp(nodeHome string) (*coreumapp.App, []byte) {
-	db := dbm.NewMemDB()
-	appInstance := coreumapp.New(
+// NewApp creates the app instance on top of the provided db, loading the latest committed state from it.
+// The chosen network is global too, it is only read while the app is created and its genesis generated,
+// which the callers serialize across the envs.
+func NewApp(db dbm.DB, nodeHome string, cfg Config) (*coreumapp.App, error) {
+	if err := setSDKConfig(cfg.AddressPrefix); err != nil {
+		return nil, err
+	}
+	coreumapp.ChosenNetwork = newNetworkConfig(cfg)
+
+	return coreumapp.New(
 		log.NewNopLogger(),
 		db,
 		nil,
 		true,
 		simtestutil.NewAppOptionsWithFlagHome(nodeHome),
-		baseapp.SetChainID(string(NetworkConfig.ChainID())),
-	)
+		baseapp.SetChainID(cfg.ChainID),
+	), nil
+}
 
-	networkProvider, ok := NetworkConfig.Provider.(coreumconfig.DynamicConfigProvider)
+func SetupApp(nodeHome string, db dbm.DB, cfg Config) (*coreumapp.App, []byte, error) {
+	appInstance, err := NewApp(db, nodeHome, cfg)
+	if err != nil {
+		return nil, nil, err
+	}
+
+	networkProvider, ok := newNetworkConfig(cfg).Provider.(coreumconfig.DynamicConfigProvider)
 	if !ok {
 		panic("failed to cast network config provider to DynamicConfigProvider")
 	}
@@ -105,6 +118,14 @@ func SetupApp(nodeHome string) (*coreumapp.App, []byte) {
 	// register the validator and account in the genesis
 	genesisState, err = simtestutil.GenesisStateWithValSet(appInstance.AppCodec(), genesisState, valSet, []authtypes.GenesisAccount{acc})
 
+	// apply the module genesis overrides on top of the generated state
+	for module, override := range cfg.ModuleGenesis {
+		genesisState[module], err = mergeJSON(genesisState[module], override)
+		if err != nil {
+			return nil, nil, errors.Wrapf(err, "invalid genesis override for module `%s`", module)
+		}
+	}
+
 	stateBytes, err := json.MarshalIndent(genesisState, "", " ")
 	requireNoErr(err)
 
@@ -112,7 +133,7 @@ func SetupApp(nodeHome string) (*coreumapp.App, []byte) {
 	stateBytes = []byte(
 		strings.ReplaceAll(string(stateBytes),
 			fmt.Sprintf("\"%s\"", sdk.DefaultBondDenom),
-			fmt.Sprintf("\"%s\"", NetworkConfig.Denom()),
+			fmt.Sprintf("\"%s\"", cfg.Denom),
 		))
 
 	consensusParams := simtestutil.DefaultConsensusParams
@@ -122,20 +143,25 @@ func SetupApp(nodeHome string) (*coreumapp.App, []byte) {
 
 	_, err = appInstance.InitChain(
 		&abci.RequestInitChain{
-			ChainId:         string(NetworkConfig.ChainID()),
+			ChainId:         cfg.ChainID,
 			Validators:      []abci.ValidatorUpdate{},
 			ConsensusParams: consensusParams,
 			AppStateBytes:   stateBytes,
 		},
 	)
 	if err != nil {
-		panic(errors.Errorf("can't init chain: %s", err))
+		return nil, nil, errors.Errorf("can't init chain: %s", err)
 	}
 
-	return appInstance, validatorKey.Bytes()
+	return appInstance, validatorKey.Bytes(), nil
 }
 
 func (env *TestEnv) BeginNewBlock(timeIncreaseSeconds uint64) {
+	env.BeginNewBlockWithTimeIncrease(time.Duration(timeIncreaseSeconds) * time.Second)
+}
+
+// BeginNewBlockWithTimeIncrease begins a new block with the block time increased by the given duration.
+func (env *TestEnv) BeginNewBlockWithTimeIncrease(timeIncrease time.Duration) {
 	var valAddr []byte
 
 	validators, err := env.App.StakingKeeper.GetAllValidators(env.Ctx)
@@ -153,7 +179,45 @@ func (env *TestEnv) BeginNewBlock(timeIncreaseSeconds uint64) {
 		valAddr = valAddr2
 	}
 
-	env.beginNewBlockWithProposer(valAddr, timeIncreaseSeconds)
+	env.beginNewBlockWithProposer(valAddr, timeIncrease)
+}
+
+// EndBlock finalizes and commits the current block. The returned result includes the events
+// and validator updates of finalizing the block when it began.
+func (env *TestEnv) EndBlock() *abci.ResponseFinalizeBlock {
+	reqFinalizeBlock := &abci.RequestFinalizeBlock{Height: env.Ctx.BlockHeight(), Time: env.Ctx.BlockTime()}
+	res, err := env.App.FinalizeBlock(reqFinalizeBlock)
+	requireNoErr(err)
+	_, err = env.App.Commit()
+	requireNoErr(err)
+
+	if env.beginBlockResponse != nil {
+		res = mergeFinalizeBlockResponses(env.beginBlockResponse, res)
+		env.beginBlockResponse = nil
+	}
+
+	return res
+}
+
+// AdvanceBlocks produces n empty blocks, with the block time increased by the given duration for each of them.
+// The returned result includes the events and validator updates of all the blocks.
+func (env *TestEnv) AdvanceBlocks(n uint64, timeIncrease time.Duration) *abci.ResponseFinalizeBlock {
+	res := &abci.ResponseFinalizeBlock{}
+	for i := uint64(0); i < n; i++ {
+		env.BeginNewBlockWithTimeIncrease(timeIncrease)
+		res = mergeFinalizeBlockResponses(res, env.EndBlock())
+	}
+
+	return res
+}
+
+// mergeFinalizeBlockResponses appends the events and validator updates of the next response to the previous one.
+func mergeFinalizeBlockResponses(prev, next *abci.ResponseFinalizeBlock) *abci.ResponseFinalizeBlock {
+	res := *next
+	res.Events = append(append([]abci.Event{}, prev.Events...), next.Events...)
+	res.ValidatorUpdates = append(append([]abci.ValidatorUpdate{}, prev.ValidatorUpdates...), next.ValidatorUpdates...)
+
+	return &res
 }
 
 func (env *TestEnv) GetValidatorAddresses() []string {
@@ -174,7 +238,7 @@ func (env *TestEnv) GetValidatorPrivateKey() []byte {
 }
 
 // beginNewBlockWithProposer begins a new block with a proposer.
-func (env *TestEnv) beginNewBlockWithProposer(proposer sdk.ConsAddress, timeIncreaseSeconds uint64) {
+func (env *TestEnv) beginNewBlockWithProposer(proposer sdk.ConsAddress, timeIncrease time.Duration) {
 	validator, err := env.App.StakingKeeper.GetValidatorByConsAddr(env.Ctx, proposer)
 
 	if err != nil {
@@ -184,8 +248,8 @@ func (env *TestEnv) beginNewBlockWithProposer(proposer sdk.ConsAddress, timeIncr
 	valAddr, err := validator.GetConsAddr()
 	requireNoErr(err)
 
-	newBlockTime := env.Ctx.BlockTime().Add(time.Duration(timeIncreaseSeconds) * time.Second)
-	header := tmproto.Header{ChainID: string(NetworkConfig.ChainID()), Height: env.Ctx.BlockHeight() + 1, Time: newBlockTime}
+	newBlockTime := env.Ctx.BlockTime().Add(timeIncrease)
+	header := tmproto.Header{ChainID: env.Config.ChainID, Height: env.Ctx.BlockHeight() + 1, Time: newBlockTime}
 	newCtx := env.Ctx.WithBlockTime(newBlockTime).WithBlockHeight(env.Ctx.BlockHeight() + 1)
 	env.Ctx = newCtx
 	requestFinalizeBlock := &abci.RequestFinalizeBlock{
@@ -200,10 +264,11 @@ func (env *TestEnv) beginNewBlockWithProposer(proposer sdk.ConsAddress, timeIncr
 		NextValidatorsHash: nil,
 		ProposerAddress:    valAddr,
 	}
-	_, err = env.App.FinalizeBlock(requestFinalizeBlock)
+	res, err := env.App.FinalizeBlock(requestFinalizeBlock)
 	if err != nil {
 		panic(errors.Errorf("can't begin a new block: %s", err))
 	}
+	env.beginBlockResponse = res
 	env.Ctx = env.App.NewContextLegacy(false, header)
 }
 
@@ -278,25 +343,3 @@ func requireNoNil(name string, nilable any) {
 		panic(fmt.Sprintf("%s must not be nil", name))
 	}
 }
-
-func newNetworkConfig() coreumconfig.NetworkConfig {
-	networkConfig := coreumconfig.NetworkConfig{
-		Provider: coreumconfig.DynamicConfigProvider{
-			GenesisInitConfig: coreumconfig.GenesisInitConfig{
-				AddressPrefix: coreumconstant.AddressPrefixMain,
-				ChainID:       coreumconstant.ChainIDMain,
-				GenesisTime:   time.Now(),
-				Denom:         coreumconstant.DenomMain,
-				GovConfig: coreumconfig.GenesisInitGovConfig{
-					MinDeposit:   sdk.Coins{sdk.NewCoin(coreumconstant.DenomMain, math.NewInt(1000))},
-					VotingPeriod: time.Second * 10,
-				},
-				CustomParamsConfig: coreumconfig.GenesisInitCustomParamsConfig{
-					MinSelfDelegation: math.NewInt(10_000_000), // 10 core
-				},
-			},
-		},
-	}
-
-	return networkConfig
-}
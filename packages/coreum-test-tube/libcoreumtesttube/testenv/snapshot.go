@@ -0,0 +1,157 @@
+package testenv
+
+import (
+	"io"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"slices"
+	"time"
+
+	tmproto "github.com/cometbft/cometbft/proto/tendermint/types"
+	dbm "github.com/cosmos/cosmos-db"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	"github.com/pkg/errors"
+
+	coreumapp "github.com/CoreumFoundation/coreum/v5/app"
+)
+
+// Snapshot is a copy of the committed state of the TestEnv at a given block.
+type Snapshot struct {
+	db      *dbm.MemDB
+	height  int64
+	time    time.Time
+	txIndex []*sdk.TxResponse
+}
+
+// Snapshot captures the committed state of the env. It must be called between blocks.
+func (env *TestEnv) Snapshot() *Snapshot {
+	return &Snapshot{
+		db:      copyDB(env.DB),
+		height:  env.Ctx.BlockHeight(),
+		time:    env.Ctx.BlockTime(),
+		txIndex: slices.Clone(env.txIndex),
+	}
+}
+
+// Restore rewinds the env to the state captured by the snapshot.
+// The app is recreated on top of a copy of the snapshot db, so the same snapshot can be restored many times.
+func (env *TestEnv) Restore(snapshot *Snapshot) error {
+	db := copyDB(snapshot.db)
+	app, nodeHome, err := env.newAppFromDB(db)
+	if err != nil {
+		return err
+	}
+
+	if err := os.RemoveAll(env.NodeHome); err != nil {
+		return errors.Wrap(err, "can't remove node home")
+	}
+
+	env.App = app
+	env.DB = db
+	env.NodeHome = nodeHome
+	env.Ctx = env.newCommittedContext(app, snapshot.height, snapshot.time)
+	env.beginBlockResponse = nil
+	env.txIndex = slices.Clone(snapshot.txIndex)
+
+	return nil
+}
+
+// Fork creates an independent env with a copy of the committed state of the current one.
+func (env *TestEnv) Fork() (*TestEnv, error) {
+	db := copyDB(env.DB)
+	app, nodeHome, err := env.newAppFromDB(db)
+	if err != nil {
+		return nil, err
+	}
+
+	return &TestEnv{
+		App:                app,
+		DB:                 db,
+		Ctx:                env.newCommittedContext(app, env.Ctx.BlockHeight(), env.Ctx.BlockTime()),
+		ParamTypesRegistry: env.ParamTypesRegistry,
+		Validator:          env.Validator,
+		NodeHome:           nodeHome,
+		Config:             env.Config,
+		txIndex:            slices.Clone(env.txIndex),
+	}, nil
+}
+
+// newAppFromDB creates an app on top of the db. Since wasmvm locks its cache directory,
+// the new app gets a new node home with a copy of the env one.
+func (env *TestEnv) newAppFromDB(db *dbm.MemDB) (*coreumapp.App, string, error) {
+	nodeHome, err := os.MkdirTemp("", ".coreum-test-tube-temp-")
+	if err != nil {
+		return nil, "", errors.Wrap(err, "can't create node home")
+	}
+	if err := copyDir(env.NodeHome, nodeHome); err != nil {
+		return nil, "", errors.Wrap(err, "can't copy node home")
+	}
+
+	app, err := NewApp(db, nodeHome, env.Config)
+	if err != nil {
+		return nil, "", err
+	}
+
+	return app, nodeHome, nil
+}
+
+func (env *TestEnv) newCommittedContext(app *coreumapp.App, height int64, blockTime time.Time) sdk.Context {
+	return app.NewUncachedContext(false, tmproto.Header{
+		ChainID: env.Config.ChainID,
+		Height:  height,
+		Time:    blockTime,
+	})
+}
+
+func copyDB(src *dbm.MemDB) *dbm.MemDB {
+	dst := dbm.NewMemDB()
+
+	iter, err := src.Iterator(nil, nil)
+	requireNoErr(err)
+	defer iter.Close()
+
+	for ; iter.Valid(); iter.Next() {
+		requireNoErr(dst.Set(iter.Key(), iter.Value()))
+	}
+	requireNoErr(iter.Error())
+
+	return dst
+}
+
+func copyDir(src, dst string) error {
+	return filepath.WalkDir(src, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+
+		rel, err := filepath.Rel(src, path)
+		if err != nil {
+			return err
+		}
+		target := filepath.Join(dst, rel)
+
+		if d.IsDir() {
+			return os.MkdirAll(target, 0o755)
+		}
+
+		return copyFile(path, target)
+	})
+}
+
+func copyFile(src, dst string) error {
+	in, err := os.Open(src)
+	if err != nil {
+		return err
+	}
+	defer in.Close()
+
+	out, err := os.Create(dst)
+	if err != nil {
+		return err
+	}
+	defer out.Close()
+
+	_, err = io.Copy(out, in)
+	return err
+}
@@ -0,0 +1,95 @@
+package testenv
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"strconv"
+	"strings"
+	"time"
+
+	errorsmod "cosmossdk.io/errors"
+	abci "github.com/cometbft/cometbft/abci/types"
+	"github.com/cometbft/cometbft/libs/pubsub/query"
+	codectypes "github.com/cosmos/cosmos-sdk/codec/types"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	txtypes "github.com/cosmos/cosmos-sdk/types/tx"
+	"github.com/cosmos/gogoproto/proto"
+	"github.com/pkg/errors"
+)
+
+// IndexTx records the transaction executed in the current block with its result,
+// failed transactions are recorded with the code of their error. SimDeliver does not return
+// the events of failed transactions, not even the ones of the ante handler, so those are
+// recorded without events and only match queries on tx.hash and tx.height.
+func (env *TestEnv) IndexTx(txBytes []byte, gasInfo sdk.GasInfo, res *sdk.Result, txErr error) {
+	hash := sha256.Sum256(txBytes)
+	txRes := &sdk.TxResponse{
+		Height:    env.Ctx.BlockHeight(),
+		TxHash:    strings.ToUpper(hex.EncodeToString(hash[:])),
+		GasWanted: int64(gasInfo.GasWanted),
+		GasUsed:   int64(gasInfo.GasUsed),
+		Timestamp: env.Ctx.BlockTime().Format(time.RFC3339),
+	}
+
+	// malformed transactions are indexed without their content
+	var tx txtypes.Tx
+	if err := env.App.AppCodec().Unmarshal(txBytes, &tx); err == nil {
+		if anyTx, err := codectypes.NewAnyWithValue(&tx); err == nil {
+			txRes.Tx = anyTx
+		}
+	}
+
+	if txErr != nil {
+		txRes.Codespace, txRes.Code, txRes.RawLog = errorsmod.ABCIInfo(txErr, false)
+	} else {
+		data, err := proto.Marshal(&sdk.TxMsgData{MsgResponses: res.MsgResponses})
+		requireNoErr(err)
+		txRes.Data = strings.ToUpper(hex.EncodeToString(data))
+		txRes.RawLog = res.Log
+		txRes.Events = res.Events
+	}
+
+	env.txIndex = append(env.txIndex, txRes)
+}
+
+// FindTx returns the indexed transaction with the given hex encoded hash.
+func (env *TestEnv) FindTx(hash string) (*sdk.TxResponse, bool) {
+	for _, txRes := range env.txIndex {
+		if strings.EqualFold(txRes.TxHash, hash) {
+			return txRes, true
+		}
+	}
+
+	return nil, false
+}
+
+// SearchTxs returns the indexed transactions matching the query in the CometBFT tx_search grammar,
+// e.g. `message.sender='core1...' AND tx.height>5`, in the order they were executed.
+func (env *TestEnv) SearchTxs(q string) ([]*sdk.TxResponse, error) {
+	parsed, err := query.New(q)
+	if err != nil {
+		return nil, errors.Wrap(err, "invalid query")
+	}
+
+	txs := []*sdk.TxResponse{}
+	for _, txRes := range env.txIndex {
+		// the hash and height are indexed by CometBFT as attributes of the tx event
+		events := append([]abci.Event{{
+			Type: "tx",
+			Attributes: []abci.EventAttribute{
+				{Key: "hash", Value: txRes.TxHash},
+				{Key: "height", Value: strconv.FormatInt(txRes.Height, 10)},
+			},
+		}}, txRes.Events...)
+
+		matches, err := parsed.Matches(events)
+		if err != nil {
+			return nil, errors.Wrap(err, "can't match query")
+		}
+		if matches {
+			txs = append(txs, txRes)
+		}
+	}
+
+	return txs, nil
+}
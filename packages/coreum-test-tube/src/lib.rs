@@ -5,9 +5,25 @@ pub use coreum_wasm_sdk;
 pub use cosmrs;
 
 pub use module::*;
-pub use runner::app::CoreumTestApp;
-pub use test_tube_coreum::account::{Account, FeeSetting, NonSigningAccount, SigningAccount};
+pub use runner::app::{CoreumTestApp, HD_PATH};
+pub use runner::builder::CoreumTestAppBuilder;
+pub use runner::error::{AssetFtError, ChainError, DexError, SdkError};
+pub use runner::event::{TypedEvent, TypedEvents};
+pub use runner::network::Network;
+pub use test_tube_coreum::account::{
+    Account, FeeSetting, MultisigAccount, NonSigningAccount, Signer, SigningAccount,
+};
+pub use test_tube_coreum::runner::block::Block;
 pub use test_tube_coreum::runner::error::{DecodeError, EncodeError, RunnerError};
-pub use test_tube_coreum::runner::result::{ExecuteResponse, RunnerExecuteResult, RunnerResult};
+pub use test_tube_coreum::runner::event::{EventMatcher, EventQuery, Events};
+pub use test_tube_coreum::runner::gas::{
+    GasRecord, GasRecordKind, GasRecorder, GasRegression, GasReport, GasReportEntry,
+};
+pub use test_tube_coreum::runner::options::{SignMode, TxOptions};
+pub use test_tube_coreum::runner::result::{
+    BlockResult, CheckTxResult, ExecuteMultiResponse, ExecuteResponse, RunnerExecuteResult,
+    RunnerResult,
+};
+pub use test_tube_coreum::runner::snapshot::Snapshot;
 pub use test_tube_coreum::runner::Runner;
-pub use test_tube_coreum::{fn_execute, fn_query};
+pub use test_tube_coreum::{assert_event, assert_no_event, fn_execute, fn_query};
@@ -63,7 +63,7 @@ where
 mod tests {
     use coreum_wasm_sdk::types::coreum::asset::ft::v1::{DexSettings, Feature, MsgIssue};
     use coreum_wasm_sdk::types::coreum::dex::v1::{
-        MsgCancelOrder, MsgCancelOrdersByDenom, MsgPlaceOrder, OrderType,
+        EventOrderPlaced, MsgCancelOrder, MsgCancelOrdersByDenom, MsgPlaceOrder, OrderType,
         QueryAccountDenomOrdersCountRequest, QueryOrderBookOrdersRequest,
         QueryOrderBookParamsRequest, QueryOrderBooksRequest, QueryOrderRequest, QueryOrdersRequest,
         QueryParamsRequest, Side, TimeInForce,
@@ -73,7 +73,7 @@ mod tests {
     use cosmwasm_std::Coin;
 
     use crate::runner::app::FEE_DENOM;
-    use crate::{Account, AssetFT, CoreumTestApp, Dex, Module};
+    use crate::{Account, AssetFT, CoreumTestApp, Dex, Module, TypedEvents};
 
     #[test]
     fn dex_testing() {
@@ -175,7 +175,11 @@ mod tests {
             time_in_force: TimeInForce::Gtc as i32,
         };
 
-        dex.place_order(msg_place_order.clone(), &acc1).unwrap();
+        let res = dex.place_order(msg_place_order.clone(), &acc1).unwrap();
+        let placed = res.typed_events::<EventOrderPlaced>().unwrap();
+        assert_eq!(placed.len(), 1);
+        assert_eq!(placed[0].creator, acc1.address());
+        assert_eq!(placed[0].id, "id");
 
         let request_order_book_params = dex
             .query_order_book_params(&QueryOrderBookParamsRequest {
@@ -186,4 +186,135 @@ mod tests {
         assert_eq!(admin_list.admins, vec![user.address()]);
         assert!(admin_list.mutable);
     }
+
+    #[test]
+    fn wasm_events() {
+        use crate::{EventMatcher, Events};
+
+        let app = CoreumTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, FEE_DENOM)])
+            .unwrap();
+        let user = app.init_account(&[]).unwrap();
+
+        let wasm = Wasm::new(&app);
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, &signer)
+            .unwrap()
+            .data
+            .code_id;
+        let contract_addr = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![signer.address()],
+                    mutable: true,
+                },
+                None,
+                "label".into(),
+                &[],
+                &signer,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        let res = wasm
+            .execute::<ExecuteMsg>(
+                &contract_addr,
+                &ExecuteMsg::UpdateAdmins {
+                    admins: vec![user.address()],
+                },
+                &[],
+                &signer,
+            )
+            .unwrap();
+
+        assert_eq!(res.wasm_events(&contract_addr).len(), 1);
+        assert!(res.wasm_events(&user.address()).is_empty());
+        assert_eq!(
+            res.wasm_attr(&contract_addr, "action"),
+            Some("update_admins")
+        );
+        assert!(
+            res.has_event(&EventMatcher::wasm(&contract_addr).with_attr("action", "update_admins"))
+        );
+        crate::assert_event!(res, "execute", "_contract_address" => &contract_addr);
+        crate::assert_no_event!(res, "instantiate");
+    }
+
+    #[test]
+    fn wasm_batched_instantiate() {
+        use coreum_wasm_sdk::types::cosmwasm::wasm::v1::{
+            MsgInstantiateContract, MsgInstantiateContractResponse,
+        };
+
+        use crate::Runner;
+
+        let app = CoreumTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, FEE_DENOM)])
+            .unwrap();
+
+        let wasm = Wasm::new(&app);
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, &signer)
+            .unwrap()
+            .data
+            .code_id;
+
+        let instantiate = |label: &str| MsgInstantiateContract {
+            sender: signer.address(),
+            admin: String::new(),
+            code_id,
+            label: label.to_string(),
+            msg: serde_json::to_vec(&InstantiateMsg {
+                admins: vec![signer.address()],
+                mutable: false,
+            })
+            .unwrap(),
+            funds: vec![],
+        };
+
+        let res = app
+            .execute_multiple_all(
+                &[
+                    (
+                        instantiate("first"),
+                        "/cosmwasm.wasm.v1.MsgInstantiateContract",
+                    ),
+                    (
+                        instantiate("second"),
+                        "/cosmwasm.wasm.v1.MsgInstantiateContract",
+                    ),
+                ],
+                &signer,
+            )
+            .unwrap();
+
+        let addresses = res
+            .decode_all::<MsgInstantiateContractResponse>()
+            .unwrap()
+            .into_iter()
+            .map(|res| res.address)
+            .collect::<Vec<_>>();
+        assert_eq!(addresses.len(), 2);
+        assert_ne!(addresses[0], addresses[1]);
+        assert_eq!(
+            res.decode::<MsgInstantiateContractResponse>(1)
+                .unwrap()
+                .address,
+            addresses[1]
+        );
+        assert!(res.decode::<MsgInstantiateContractResponse>(2).is_err());
+
+        for address in addresses {
+            let admin_list = wasm
+                .query::<QueryMsg, AdminListResponse>(&address, &QueryMsg::AdminList {})
+                .unwrap();
+            assert_eq!(admin_list.admins, vec![signer.address()]);
+        }
+    }
 }
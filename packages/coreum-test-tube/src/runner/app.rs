@@ -1,24 +1,43 @@
+use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
 use cosmrs::Any;
 
 use cosmwasm_std::{Coin, Timestamp};
 
 use prost::Message;
 use serde::de::DeserializeOwned;
-use test_tube_coreum::account::SigningAccount;
-use test_tube_coreum::runner::result::{RunnerExecuteResult, RunnerResult};
+use test_tube_coreum::account::{MultisigAccount, SigningAccount};
+use test_tube_coreum::runner::block::Block;
+use test_tube_coreum::runner::event::EventQuery;
+use test_tube_coreum::runner::gas::GasRecorder;
+use test_tube_coreum::runner::options::TxOptions;
+use test_tube_coreum::runner::result::{
+    BlockResult, CheckTxResult, RunnerExecuteResult, RunnerResult,
+};
+use test_tube_coreum::runner::snapshot::Snapshot;
 use test_tube_coreum::runner::Runner;
 use test_tube_coreum::BaseApp;
 
+use crate::runner::builder::CoreumTestAppBuilder;
+use crate::runner::network::Network;
+
 pub const FEE_DENOM: &str = "ucore";
-const ADDRESS_PREFIX: &str = "core";
-const CHAIN_ID: &str = "coreum-mainnet-1";
-const DEFAULT_GAS_ADJUSTMENT: f64 = 1.2;
+pub(crate) const ADDRESS_PREFIX: &str = "core";
+pub(crate) const CHAIN_ID: &str = "coreum-mainnet-1";
+pub(crate) const DEFAULT_GAS_ADJUSTMENT: f64 = 1.2;
+/// Coreum HD derivation path (coin type 990) for the first account.
+pub const HD_PATH: &str = "m/44'/990'/0'/0/0";
 
+/// Coreum app, which can be shared across threads like the [`BaseApp`] it wraps.
 #[derive(Debug, PartialEq)]
 pub struct CoreumTestApp {
-    inner: BaseApp,
+    pub(crate) inner: BaseApp,
 }
 
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<CoreumTestApp>();
+};
+
 impl Default for CoreumTestApp {
     fn default() -> Self {
         CoreumTestApp::new()
@@ -32,6 +51,39 @@ impl CoreumTestApp {
         }
     }
 
+    /// Create a new app configured for the given network.
+    /// The address prefix is global to the Go library, so all apps of a process must be of
+    /// networks with the same prefix, creating an app with another prefix fails.
+    pub fn new_with_network(network: Network) -> RunnerResult<Self> {
+        Self::builder().network(network).build()
+    }
+
+    /// Create a builder to customize the network settings and the genesis of the app
+    pub fn builder() -> CoreumTestAppBuilder {
+        CoreumTestAppBuilder::new()
+    }
+
+    /// Create a new app with its state initialized from the given genesis,
+    /// e.g. one exported with `export_genesis` or a curated genesis file.
+    /// The app uses the chain id of the genesis and the mainnet address prefix and denom,
+    /// use [`CoreumTestAppBuilder::build_from_genesis`] for the genesis of other networks.
+    pub fn from_genesis(genesis: &serde_json::Value) -> RunnerResult<Self> {
+        Self::builder().build_from_genesis(genesis)
+    }
+
+    /// Export the current chain state as a genesis
+    pub fn export_genesis(&self) -> RunnerResult<serde_json::Value> {
+        self.inner.export_genesis()
+    }
+
+    /// Create an independent copy of the app with the same chain state,
+    /// which can diverge from the original without affecting it
+    pub fn fork(&self) -> Self {
+        Self {
+            inner: self.inner.fork(),
+        }
+    }
+
     /// Get the current block time as a timestamp
     pub fn get_block_timestamp(&self) -> Timestamp {
         self.inner.get_block_timestamp()
@@ -52,6 +104,36 @@ impl CoreumTestApp {
         self.inner.get_block_height()
     }
 
+    /// Produce a new block with the given block time, which must be after the current one
+    pub fn set_block_time(&self, time: Timestamp) -> RunnerResult<BlockResult> {
+        self.inner.set_block_time(time)
+    }
+
+    /// Produce empty blocks, with the block time increased by `seconds_per_block` for each of them
+    pub fn advance_blocks(&self, blocks: u64, seconds_per_block: u64) -> RunnerResult<BlockResult> {
+        self.inner.advance_blocks(blocks, seconds_per_block)
+    }
+
+    /// Produce empty blocks until the chain reaches the given height,
+    /// with the block time increased by `seconds_per_block` for each of them
+    pub fn advance_to_height(
+        &self,
+        height: i64,
+        seconds_per_block: u64,
+    ) -> RunnerResult<BlockResult> {
+        self.inner.advance_to_height(height, seconds_per_block)
+    }
+
+    /// Take a snapshot of the current chain state, which can be restored later
+    pub fn snapshot(&self) -> Snapshot {
+        self.inner.snapshot()
+    }
+
+    /// Rewind the chain to the state captured by the given snapshot of this app
+    pub fn restore(&self, snapshot: &Snapshot) -> RunnerResult<()> {
+        self.inner.restore(snapshot)
+    }
+
     /// Get the first validator address
     pub fn get_first_validator_address(&self) -> RunnerResult<String> {
         self.inner.get_first_validator_address()
@@ -62,22 +144,118 @@ impl CoreumTestApp {
         self.inner.get_first_validator_signing_account()
     }
 
-    /// Increase the time of the blockchain by the given number of seconds.
-    pub fn increase_time(&self, seconds: u64) {
+    /// Increase the time of the blockchain by the given number of seconds,
+    /// returning the events and validator updates of the produced block.
+    pub fn increase_time(&self, seconds: u64) -> RunnerResult<BlockResult> {
         self.inner.increase_time(seconds)
     }
 
+    /// Deliver all transactions executed through the given block runner in a single block.
+    /// Returns the output of `execution` and the result of finalizing the block.
+    pub fn block<T>(&self, execution: impl FnOnce(&Block) -> T) -> RunnerResult<(T, BlockResult)> {
+        self.inner.block(execution)
+    }
+
     /// Initialize account with initial balance of any coins.
     /// This function mints new coins and send to newly created account
     pub fn init_account(&self, coins: &[Coin]) -> RunnerResult<SigningAccount> {
         self.inner.init_account(coins)
     }
+
+    /// Initialize account derived from the mnemonic with the given HD path,
+    /// e.g. [`HD_PATH`], with initial balance of any coins.
+    pub fn init_account_from_mnemonic(
+        &self,
+        mnemonic: &str,
+        hd_path: &str,
+        coins: &[Coin],
+    ) -> RunnerResult<SigningAccount> {
+        self.inner
+            .init_account_from_mnemonic(mnemonic, hd_path, coins)
+    }
+
+    /// Initialize account with the private key derived from the seed,
+    /// with initial balance of any coins.
+    pub fn init_account_with_seed(
+        &self,
+        seed: &str,
+        coins: &[Coin],
+    ) -> RunnerResult<SigningAccount> {
+        self.inner.init_account_with_seed(seed, coins)
+    }
+
+    /// Initialize multisig account made of the members with the given threshold,
+    /// with initial balance of any coins.
+    pub fn init_multisig_account(
+        &self,
+        threshold: u32,
+        members: Vec<SigningAccount>,
+        coins: &[Coin],
+    ) -> RunnerResult<MultisigAccount> {
+        self.inner.init_multisig_account(threshold, members, coins)
+    }
+
+    /// Mint the coins to any bech32 address, including contract and module accounts,
+    /// without going through the ante handler.
+    pub fn fund_address(&self, address: &str, coins: &[Coin]) -> RunnerResult<()> {
+        self.inner.fund_address(address, coins)
+    }
+
     /// Convinience function to create multiple accounts with the same
     /// Initial coins balance
     pub fn init_accounts(&self, coins: &[Coin], count: u64) -> RunnerResult<Vec<SigningAccount>> {
         self.inner.init_accounts(coins, count)
     }
 
+    /// Sequence of the account at the address in the last committed state.
+    pub fn account_sequence(&self, address: &str) -> RunnerResult<u64> {
+        self.inner.account_sequence(address)
+    }
+
+    /// Account number of the account at the address, which must exist.
+    pub fn account_number(&self, address: &str) -> RunnerResult<u64> {
+        self.inner.account_number(address)
+    }
+
+    /// Build and sign the transaction the same way `execute_*` methods do, without executing it.
+    pub fn build_signed_tx(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signer: &SigningAccount,
+        options: &TxOptions,
+    ) -> RunnerResult<Vec<u8>> {
+        self.inner.build_signed_tx(msgs, signer, options)
+    }
+
+    /// Execute the encoded `TxRaw` in a new block.
+    pub fn execute_tx_bytes<R>(&self, tx_bytes: &[u8]) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        self.inner.execute_tx_bytes(tx_bytes)
+    }
+
+    /// Run the encoded `TxRaw` through CheckTx, the mempool admission check of a node.
+    pub fn check_tx(&self, tx_bytes: &[u8]) -> RunnerResult<CheckTxResult> {
+        self.inner.check_tx(tx_bytes)
+    }
+
+    /// Get the recorder of the gas of every executed or simulated transaction
+    pub fn gas_recorder(&self) -> &GasRecorder {
+        self.inner.gas_recorder()
+    }
+
+    /// Get the executed transaction with the given hex encoded hash
+    pub fn query_tx(&self, hash: &str) -> RunnerResult<TxResponse> {
+        self.inner.query_tx(hash)
+    }
+
+    /// Search the executed transactions matching the query, in the grammar of the `tx_search` of a node.
+    /// Failed transactions have no events, they only match `tx.hash` and `tx.height` conditions.
+    pub fn search_txs(&self, query: &EventQuery) -> RunnerResult<Vec<TxResponse>> {
+        self.inner.search_txs(query)
+    }
+
     /// Simulate transaction execution and return gas info
     pub fn simulate_tx<I>(
         &self,
@@ -136,13 +314,92 @@ impl<'a> Runner<'a> for CoreumTestApp {
     {
         self.inner.execute_multiple_raw(msgs, signer)
     }
+
+    fn execute_multi_signed_with_options<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signers: &[&SigningAccount],
+        fee_payer: &SigningAccount,
+        options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: prost::Message + Default,
+    {
+        self.inner
+            .execute_multi_signed_with_options(msgs, signers, fee_payer, options)
+    }
+
+    fn execute_multisig_with_options<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        multisig: &MultisigAccount,
+        options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: prost::Message + Default,
+    {
+        self.inner
+            .execute_multisig_with_options(msgs, multisig, options)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use coreum_wasm_sdk::types::cosmos::bank::v1beta1::{
+        MsgSend, MsgSendResponse, QueryBalanceRequest,
+    };
+    use coreum_wasm_sdk::types::cosmos::base::v1beta1::Coin as BaseCoin;
     use cosmwasm_std::coins;
+    use prost::Message;
+
+    use crate::runner::app::{CoreumTestApp, CHAIN_ID, FEE_DENOM, HD_PATH};
+    use crate::{
+        Account, Bank, Module, Runner, RunnerError, RunnerExecuteResult, SignMode, SigningAccount,
+        TxOptions,
+    };
 
-    use crate::runner::app::{CoreumTestApp, FEE_DENOM};
+    /// Message sending `amount` of the fee denom.
+    fn send_msg(from: &str, to: &str, amount: u128) -> MsgSend {
+        MsgSend {
+            from_address: from.to_string(),
+            to_address: to.to_string(),
+            amount: vec![BaseCoin {
+                amount: amount.to_string(),
+                denom: FEE_DENOM.to_string(),
+            }],
+        }
+    }
+
+    /// [`send_msg`] encoded for the functions taking raw messages.
+    fn send_any(from: &str, to: &str, amount: u128) -> cosmrs::Any {
+        cosmrs::Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: send_msg(from, to, amount).encode_to_vec(),
+        }
+    }
+
+    /// Send `amount` of the fee denom from the sender with the runner, e.g. an app or a block.
+    fn send<'a, R: Runner<'a>>(
+        runner: &'a R,
+        sender: &SigningAccount,
+        to: &str,
+        amount: u128,
+    ) -> RunnerExecuteResult<MsgSendResponse> {
+        Bank::new(runner).send(send_msg(&sender.address(), to, amount), sender)
+    }
+
+    /// Balance of the address in the fee denom.
+    fn balance<'a, R: Runner<'a>>(runner: &'a R, address: &str) -> String {
+        Bank::new(runner)
+            .query_balance(&QueryBalanceRequest {
+                address: address.to_string(),
+                denom: FEE_DENOM.to_string(),
+            })
+            .unwrap()
+            .balance
+            .unwrap()
+            .amount
+    }
 
     #[test]
     fn test_init_accounts() {
@@ -157,6 +414,654 @@ mod tests {
         assert!(accounts.get(3).is_none());
     }
 
+    #[test]
+    fn test_execute_with_options() {
+        use cosmrs::proto::cosmos::feegrant::v1beta1::{
+            BasicAllowance, MsgGrantAllowance, MsgGrantAllowanceResponse,
+        };
+
+        let app = CoreumTestApp::default();
+        let granter = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let grantee = app.init_account(&coins(1_000, FEE_DENOM)).unwrap();
+
+        app.execute::<_, MsgGrantAllowanceResponse>(
+            MsgGrantAllowance {
+                granter: granter.address(),
+                grantee: grantee.address(),
+                allowance: Some(cosmrs::Any {
+                    type_url: "/cosmos.feegrant.v1beta1.BasicAllowance".to_string(),
+                    value: BasicAllowance::default().encode_to_vec(),
+                }),
+            },
+            "/cosmos.feegrant.v1beta1.MsgGrantAllowance",
+            &granter,
+        )
+        .unwrap();
+
+        let send = send_msg(&grantee.address(), &granter.address(), 1_000);
+
+        // the fee is paid by the granter, so the grantee can send its whole balance
+        app.execute_with_options::<_, MsgSendResponse>(
+            send.clone(),
+            "/cosmos.bank.v1beta1.MsgSend",
+            &grantee,
+            &TxOptions::new()
+                .with_memo("sponsored")
+                .with_fee_granter(granter.address()),
+        )
+        .unwrap();
+        assert_eq!(balance(&app, &grantee.address()), "0");
+
+        // expired transaction
+        let err = app
+            .execute_with_options::<_, MsgSendResponse>(
+                send,
+                "/cosmos.bank.v1beta1.MsgSend",
+                &granter,
+                &TxOptions::new()
+                    .with_timeout_height(1)
+                    .with_gas_limit(200_000),
+            )
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+    }
+
+    #[test]
+    fn test_execute_with_amino_json_sign_mode() {
+        let app = CoreumTestApp::default();
+        let sender = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+
+        app.execute_with_options::<_, MsgSendResponse>(
+            send_msg(&sender.address(), &receiver.address(), 1_000),
+            "/cosmos.bank.v1beta1.MsgSend",
+            &sender,
+            &TxOptions::new()
+                .with_memo("signed with amino json")
+                .with_sign_mode(SignMode::LegacyAminoJson),
+        )
+        .unwrap();
+        assert_eq!(balance(&app, &receiver.address()), "1000");
+    }
+
+    #[test]
+    fn test_execute_with_amino_json_sign_mode_and_account_number() {
+        let app = CoreumTestApp::default();
+        let sender = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+        let account_number = app.inner.account_number(&sender.address()).unwrap();
+
+        let send = send_msg(&sender.address(), &receiver.address(), 1_000);
+        let options = TxOptions::new()
+            .with_sign_mode(SignMode::LegacyAminoJson)
+            .with_gas_limit(200_000);
+
+        // the signature covers the overridden account number, not the one on chain
+        let err = app
+            .execute_with_options::<_, MsgSendResponse>(
+                send.clone(),
+                "/cosmos.bank.v1beta1.MsgSend",
+                &sender,
+                &options.clone().with_account_number(account_number + 1),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RunnerError::ExecuteError { code: 4, ref codespace, .. } if codespace == "sdk"
+        ));
+
+        app.execute_with_options::<_, MsgSendResponse>(
+            send,
+            "/cosmos.bank.v1beta1.MsgSend",
+            &sender,
+            &options.with_account_number(account_number),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_execute_with_external_signer() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        use cosmrs::crypto::secp256k1::SigningKey;
+        use cosmrs::crypto::PublicKey;
+        use cosmrs::ErrorReport;
+        use cosmwasm_std::Coin;
+        use test_tube_coreum::runner::app::MIN_GAS_PRICE;
+
+        use crate::{FeeSetting, Signer};
+
+        // stands for a remote signing service
+        struct MockKms {
+            key: SigningKey,
+            sign_requests: Arc<AtomicUsize>,
+        }
+
+        impl Signer for MockKms {
+            fn public_key(&self) -> PublicKey {
+                self.key.public_key()
+            }
+
+            fn sign(&self, sign_bytes: &[u8]) -> Result<Vec<u8>, ErrorReport> {
+                self.sign_requests.fetch_add(1, Ordering::SeqCst);
+                Ok(self.key.sign(sign_bytes)?.to_vec())
+            }
+        }
+
+        let app = CoreumTestApp::default();
+        let sign_requests = Arc::new(AtomicUsize::new(0));
+        let sender = SigningAccount::from_signer(
+            "core".to_string(),
+            MockKms {
+                key: SigningKey::from_slice(&[7u8; 32]).unwrap(),
+                sign_requests: sign_requests.clone(),
+            },
+            FeeSetting::Auto {
+                gas_price: Coin::new(MIN_GAS_PRICE, FEE_DENOM),
+                gas_adjustment: 1.2,
+            },
+        );
+        app.fund_address(&sender.address(), &coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+        assert!(sender.signer().signing_key().is_none());
+        assert!(receiver.signer().signing_key().is_some());
+
+        send(&app, &sender, &receiver.address(), 1_000).unwrap();
+
+        // simulation and delivery
+        assert_eq!(sign_requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_execute_response_metadata() {
+        use cosmwasm_std::Coin;
+
+        use crate::FeeSetting;
+
+        let app = CoreumTestApp::default();
+        let sender = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap()
+            .with_fee_setting(FeeSetting::Custom {
+                amount: Coin::new(1_000_000u128, FEE_DENOM),
+                gas_limit: 200_000,
+            });
+        let receiver = app.init_account(&[]).unwrap();
+
+        let res = send(&app, &sender, &receiver.address(), 1_000).unwrap();
+
+        assert_eq!(res.tx_hash.len(), 64);
+        assert_eq!(res.tx_hash, res.tx_hash.to_uppercase());
+        assert_eq!(res.height, app.get_block_height() as u64);
+        assert_eq!(res.block_time.nanos(), app.get_block_time_nanos() as u64);
+        assert_eq!(res.code, 0);
+        assert_eq!(res.codespace, "");
+        assert_eq!(res.fee, coins(1_000_000, FEE_DENOM));
+    }
+
+    #[test]
+    fn test_execute_response_events() {
+        use crate::{EventMatcher, Events};
+
+        let app = CoreumTestApp::default();
+        let sender = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+
+        let res = send(&app, &sender, &receiver.address(), 1_000).unwrap();
+
+        assert!(!res.find_events("transfer").is_empty());
+        assert!(res.find_events("wasm").is_empty());
+        assert_eq!(
+            res.attr("message", "action"),
+            Some("/cosmos.bank.v1beta1.MsgSend")
+        );
+        assert_eq!(res.attr("message", "unknown"), None);
+        assert!(res.has_event(
+            &EventMatcher::new("transfer")
+                .with_attr("recipient", receiver.address())
+                .with_attr("amount", format!("1000{}", FEE_DENOM))
+                .with_key("sender")
+        ));
+        assert!(
+            !res.has_event(&EventMatcher::new("transfer").with_attr("recipient", sender.address()))
+        );
+
+        crate::assert_event!(res, "transfer", "recipient" => receiver.address());
+        crate::assert_event!(res.events, "coin_received", "receiver" => receiver.address());
+        crate::assert_no_event!(res, "transfer", "amount" => format!("1{}", FEE_DENOM));
+        crate::assert_no_event!(res, "burn");
+    }
+
+    #[test]
+    fn test_query_and_search_txs() {
+        use crate::EventQuery;
+
+        let app = CoreumTestApp::default();
+        let sender = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receivers = app.init_accounts(&[], 2).unwrap();
+
+        let responses = receivers
+            .iter()
+            .map(|receiver| send(&app, &sender, &receiver.address(), 1_000).unwrap())
+            .collect::<Vec<_>>();
+
+        let tx = app.query_tx(&responses[0].tx_hash).unwrap();
+        assert_eq!(tx.txhash, responses[0].tx_hash);
+        assert_eq!(tx.height, responses[0].height as i64);
+        assert_eq!(tx.code, 0);
+        assert!(tx.tx.is_some());
+        assert_eq!(
+            app.query_tx(&responses[0].tx_hash.to_lowercase())
+                .unwrap()
+                .txhash,
+            responses[0].tx_hash
+        );
+        assert!(matches!(
+            app.query_tx(&"0".repeat(64)).unwrap_err(),
+            RunnerError::QueryError { .. }
+        ));
+
+        let txs = app
+            .search_txs(
+                &EventQuery::new()
+                    .with_eq("message.sender", sender.address())
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            txs.iter().map(|tx| tx.txhash.clone()).collect::<Vec<_>>(),
+            vec![responses[0].tx_hash.clone(), responses[1].tx_hash.clone()]
+        );
+
+        let txs = app
+            .search_txs(
+                &EventQuery::new()
+                    .with_eq("message.sender", sender.address())
+                    .unwrap()
+                    .with_eq("transfer.recipient", receivers[1].address())
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].txhash, responses[1].tx_hash);
+
+        let txs = app
+            .search_txs(&EventQuery::new().with_height(responses[0].height))
+            .unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].txhash, responses[0].tx_hash);
+
+        let query = format!("tx.height>{}", responses[0].height);
+        assert_eq!(app.search_txs(&query.as_str().into()).unwrap().len(), 1);
+        assert!(app
+            .search_txs(
+                &EventQuery::new()
+                    .with_eq("message.sender", receivers[0].address())
+                    .unwrap()
+            )
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            app.search_txs(&"message.sender=".into()).unwrap_err(),
+            RunnerError::QueryError { .. }
+        ));
+        assert!(matches!(
+            EventQuery::new().with_eq("wasm.action", "x' OR tx.height>0 AND wasm.action='x"),
+            Err(RunnerError::GenericError(_))
+        ));
+    }
+
+    #[test]
+    fn test_gas_recorder() {
+        use crate::{GasRecordKind, GasReport};
+
+        let app = CoreumTestApp::default();
+        let sender = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+        let transfer = || send(&app, &sender, &receiver.address(), 1_000).unwrap();
+
+        let res = transfer();
+        transfer();
+        app.gas_recorder().labeled("labeled", transfer);
+
+        let records = app.gas_recorder().records();
+        // each execution is simulated first to calculate the fee
+        assert_eq!(records.len(), 6);
+        assert_eq!(records[1].kind, GasRecordKind::Execute);
+        assert_eq!(records[1].type_urls, vec!["/cosmos.bank.v1beta1.MsgSend"]);
+        assert_eq!(records[1].gas_used, res.gas_info.gas_used);
+        assert_eq!(records[1].label, None);
+        assert_eq!(records[5].label, Some("labeled".to_string()));
+
+        let report = app.gas_recorder().report();
+        assert_eq!(report.entries.len(), 4);
+        let execute = report
+            .entries
+            .iter()
+            .find(|entry| entry.label.is_empty() && entry.kind == GasRecordKind::Execute)
+            .unwrap();
+        assert_eq!(execute.count, 2);
+        assert_eq!(execute.type_urls, "/cosmos.bank.v1beta1.MsgSend");
+
+        assert_eq!(GasReport::from_json(&report.to_json()).unwrap(), report);
+        let csv = report.to_csv();
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.starts_with("label,type_urls,kind,"));
+
+        assert!(report.check_baseline(&report, 0.0).is_ok());
+        assert!(report.check_baseline(&GasReport::default(), 0.0).is_ok());
+
+        let mut baseline = report.clone();
+        for entry in baseline.entries.iter_mut() {
+            entry.max_gas_used = entry.max_gas_used * 9 / 10;
+        }
+        assert_eq!(report.regressions(&baseline, 0.05).len(), 4);
+        assert!(report.regressions(&baseline, 0.2).is_empty());
+        assert!(report.check_baseline(&baseline, 0.05).is_err());
+
+        app.gas_recorder().clear();
+        assert!(app.gas_recorder().records().is_empty());
+    }
+
+    #[test]
+    fn test_gas_recorder_failed_txs_and_panics() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        use crate::GasRecordKind;
+
+        let app = CoreumTestApp::default();
+        let sender = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+
+        // more than the balance of the sender
+        let err = app
+            .execute_with_options::<_, MsgSendResponse>(
+                send_msg(&sender.address(), &receiver.address(), 200_000_000_000),
+                "/cosmos.bank.v1beta1.MsgSend",
+                &sender,
+                &TxOptions::new().with_gas_limit(200_000),
+            )
+            .unwrap_err();
+        let RunnerError::ExecuteError { gas_used, .. } = err else {
+            panic!("unexpected error: {:?}", err);
+        };
+
+        let records = app.gas_recorder().records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].kind, GasRecordKind::Execute);
+        assert_eq!(records[0].gas_wanted, 200_000);
+        assert_eq!(records[0].gas_used, gas_used);
+
+        // the previous label is restored even if the labeled closure panics
+        app.gas_recorder().set_label(Some("outer"));
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            app.gas_recorder()
+                .labeled("inner", || panic!("failed scenario"))
+        }));
+        assert!(res.is_err());
+
+        send(&app, &sender, &receiver.address(), 1_000).unwrap();
+        let records = app.gas_recorder().records();
+        assert_eq!(records.last().unwrap().label, Some("outer".to_string()));
+    }
+
+    #[test]
+    fn test_execute_tx_bytes() {
+        let app = CoreumTestApp::default();
+        let sender = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+
+        let send = send_any(&sender.address(), &receiver.address(), 1_000);
+        let tx = app
+            .build_signed_tx(vec![send], &sender, &TxOptions::default())
+            .unwrap();
+
+        app.execute_tx_bytes::<MsgSendResponse>(&tx).unwrap();
+
+        // replayed transaction has a stale sequence
+        let err = app.execute_tx_bytes::<MsgSendResponse>(&tx).unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+
+        // malformed transaction
+        let err = app
+            .execute_tx_bytes::<MsgSendResponse>(&[1, 2, 3])
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+
+        assert_eq!(balance(&app, &receiver.address()), "1000");
+    }
+
+    #[test]
+    fn test_presigned_sequences() {
+        let app = CoreumTestApp::default();
+        let sender = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+
+        let sequence = app.account_sequence(&sender.address()).unwrap();
+        assert_eq!(sequence, 0);
+        assert!(
+            app.account_number(&receiver.address()).unwrap()
+                > app.account_number(&sender.address()).unwrap()
+        );
+        assert!(app
+            .account_number("core1qyqszqgpqyqszqgpqyqszqgpqyqszqgppae928")
+            .is_err());
+
+        let send = send_any(&sender.address(), &receiver.address(), 1_000);
+        let [first, second] = [sequence, sequence + 1].map(|sequence| {
+            app.build_signed_tx(
+                vec![send.clone()],
+                &sender,
+                &TxOptions::new().with_sequence(sequence),
+            )
+            .unwrap()
+        });
+
+        // out of order
+        assert!(app.execute_tx_bytes::<MsgSendResponse>(&second).is_err());
+
+        app.execute_tx_bytes::<MsgSendResponse>(&first).unwrap();
+        app.execute_tx_bytes::<MsgSendResponse>(&second).unwrap();
+        assert_eq!(
+            app.account_sequence(&sender.address()).unwrap(),
+            sequence + 2
+        );
+
+        // replay
+        assert!(app.execute_tx_bytes::<MsgSendResponse>(&first).is_err());
+    }
+
+    #[test]
+    fn test_check_tx() {
+        use cosmwasm_std::Coin;
+
+        use crate::FeeSetting;
+
+        let app = CoreumTestApp::default();
+        let sender = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+
+        let send = send_any(&sender.address(), &receiver.address(), 1_000);
+        let tx = app
+            .build_signed_tx(vec![send.clone()], &sender, &TxOptions::default())
+            .unwrap();
+
+        let res = app.check_tx(&tx).unwrap();
+        assert!(res.is_ok(), "{}", res.log);
+        assert!(res.gas_wanted > 0);
+
+        // the sequence is already used in the check state
+        let res = app.check_tx(&tx).unwrap();
+        assert!(!res.is_ok());
+        assert_eq!(res.codespace, "sdk");
+
+        // committing a block resets the check state
+        app.increase_time(5).unwrap();
+        assert!(app.check_tx(&tx).unwrap().is_ok());
+        app.increase_time(5).unwrap();
+
+        // fee below the minimum gas price is only rejected by CheckTx
+        let sender = sender.with_fee_setting(FeeSetting::Custom {
+            amount: Coin::new(1u128, FEE_DENOM),
+            gas_limit: 200_000,
+        });
+        let err = app
+            .execute_multiple_raw_with_options::<MsgSendResponse>(
+                vec![send],
+                &sender,
+                &TxOptions::new().with_check_tx(true),
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("insufficient fee"), "{}", err);
+    }
+
+    #[test]
+    fn test_execute_multi_signed() {
+        let app = CoreumTestApp::default();
+        let alice = app.init_account(&coins(1_000, FEE_DENOM)).unwrap();
+        let bob = app.init_account(&coins(1_000, FEE_DENOM)).unwrap();
+        let payer = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receiver = app.get_first_validator_signing_account().unwrap();
+
+        let send_from = |from: &str| send_any(from, &receiver.address(), 1_000);
+
+        // both signers send their whole balance, the fee is paid by the third account
+        app.execute_multi_signed::<MsgSendResponse>(
+            vec![send_from(&alice.address()), send_from(&bob.address())],
+            &[&alice, &bob],
+            &payer,
+        )
+        .unwrap();
+        assert_eq!(balance(&app, &alice.address()), "0");
+        assert_eq!(balance(&app, &bob.address()), "0");
+        assert_ne!(balance(&app, &payer.address()), "100000000000");
+
+        // missing signature of bob
+        let err = app
+            .execute_multi_signed::<MsgSendResponse>(
+                vec![send_from(&alice.address()), send_from(&bob.address())],
+                &[&alice],
+                &payer,
+            )
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+    }
+
+    #[test]
+    fn test_execute_multisig() {
+        let app = CoreumTestApp::default();
+        let members = (0..3)
+            .map(|i| {
+                app.init_account_with_seed(&format!("member-{}", i), &[])
+                    .unwrap()
+            })
+            .collect();
+        let multisig = app
+            .init_multisig_account(2, members, &coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+        assert_eq!(
+            multisig.multisig_public_key().type_url,
+            "/cosmos.crypto.multisig.LegacyAminoPubKey"
+        );
+
+        let send = send_any(&multisig.address(), &receiver.address(), 1_000);
+
+        // signed by the first and the last member
+        let multisig = multisig.with_signing_members(vec![0, 2]).unwrap();
+        app.execute_multisig::<MsgSendResponse>(vec![send.clone()], &multisig)
+            .unwrap();
+        assert_eq!(balance(&app, &receiver.address()), "1000");
+
+        // below threshold
+        let multisig = multisig.with_signing_members(vec![1]).unwrap();
+        let err = app
+            .execute_multisig::<MsgSendResponse>(vec![send], &multisig)
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+
+        // not a member
+        let err = multisig.with_signing_members(vec![0, 3]).err();
+        assert_eq!(
+            err,
+            Some(RunnerError::GenericError(
+                "multisig has no member at index 3".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_fund_address() {
+        let app = CoreumTestApp::default();
+        let address = "core1qyqszqgpqyqszqgpqyqszqgpqyqszqgppae928";
+
+        app.fund_address(address, &coins(1_000, FEE_DENOM)).unwrap();
+        app.fund_address(address, &coins(500, FEE_DENOM)).unwrap();
+        assert_eq!(balance(&app, address), "1500");
+
+        let err = app
+            .fund_address("invalid", &coins(1_000, FEE_DENOM))
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+    }
+
+    #[test]
+    fn test_init_deterministic_accounts() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let app = CoreumTestApp::default();
+        let other_app = CoreumTestApp::default();
+
+        let from_mnemonic = app
+            .init_account_from_mnemonic(mnemonic, HD_PATH, &coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let other_from_mnemonic = other_app
+            .init_account_from_mnemonic(mnemonic, HD_PATH, &coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        assert_eq!(from_mnemonic.address(), other_from_mnemonic.address());
+
+        let alice = app
+            .init_account_with_seed("alice", &coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let other_alice = other_app
+            .init_account_with_seed("alice", &coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        let bob = app
+            .init_account_with_seed("bob", &coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        assert_eq!(alice.address(), other_alice.address());
+        assert_ne!(alice.address(), bob.address());
+        assert_eq!(balance(&app, &alice.address()), "100000000000");
+
+        assert!(matches!(
+            app.init_account_from_mnemonic("not a mnemonic", HD_PATH, &[]),
+            Err(RunnerError::DecodeError(_))
+        ));
+    }
+
     #[test]
     fn test_get_and_set_block_timestamp() {
         let app = CoreumTestApp::default();
@@ -164,7 +1069,7 @@ mod tests {
         let block_time_nanos = app.get_block_time_nanos();
         let block_time_seconds = app.get_block_time_seconds();
 
-        app.increase_time(10u64);
+        app.increase_time(10u64).unwrap();
 
         assert_eq!(
             app.get_block_time_nanos(),
@@ -179,8 +1084,275 @@ mod tests {
 
         assert_eq!(app.get_block_height(), 1i64);
 
-        app.increase_time(10u64);
+        app.increase_time(10u64).unwrap();
 
         assert_eq!(app.get_block_height(), 2i64);
     }
+
+    #[test]
+    fn test_set_block_time() {
+        let app = CoreumTestApp::default();
+        let height = app.get_block_height();
+
+        let block_time = app.get_block_timestamp().plus_nanos(1_500_000_000);
+        app.set_block_time(block_time).unwrap();
+
+        assert_eq!(app.get_block_timestamp(), block_time);
+        assert_eq!(app.get_block_height(), height + 1);
+
+        // time can't go backwards
+        let err = app.set_block_time(block_time.minus_seconds(1)).unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+
+        // time past the range of the chain
+        let err = app
+            .set_block_time(cosmwasm_std::Timestamp::from_nanos(u64::MAX))
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::GenericError(_)));
+    }
+
+    #[test]
+    fn test_advance_blocks() {
+        let app = CoreumTestApp::default();
+        let height = app.get_block_height();
+        let block_time_seconds = app.get_block_time_seconds();
+
+        app.advance_blocks(10, 6).unwrap();
+
+        assert_eq!(app.get_block_height(), height + 10);
+        assert_eq!(app.get_block_time_seconds(), block_time_seconds + 60);
+
+        app.advance_to_height(height + 100, 2).unwrap();
+
+        assert_eq!(app.get_block_height(), height + 100);
+        assert_eq!(app.get_block_time_seconds(), block_time_seconds + 240);
+
+        assert!(app.advance_to_height(height, 1).is_err());
+    }
+
+    #[test]
+    fn test_block_events() {
+        use crate::Events;
+
+        let app = CoreumTestApp::default();
+
+        let res = app.increase_time(5).unwrap();
+        let mints_per_block = res.find_events("mint").len();
+        assert!(mints_per_block > 0);
+
+        let res = app.advance_blocks(3, 5).unwrap();
+        assert_eq!(res.find_events("mint").len(), 3 * mints_per_block);
+
+        let height = app.get_block_height();
+        let res = app.advance_to_height(height + 2, 1).unwrap();
+        assert_eq!(res.find_events("mint").len(), 2 * mints_per_block);
+        assert!(res.validator_updates.is_empty());
+
+        let res = app
+            .set_block_time(app.get_block_timestamp().plus_seconds(5))
+            .unwrap();
+        assert_eq!(res.find_events("mint").len(), mints_per_block);
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let app = CoreumTestApp::default();
+
+        let accounts = app
+            .init_accounts(&coins(100_000_000_000, FEE_DENOM), 2)
+            .unwrap();
+        let sender = &accounts[0];
+        let receiver = &accounts[1];
+
+        let snapshot = app.snapshot();
+        let height = app.get_block_height();
+        let block_time_nanos = app.get_block_time_nanos();
+
+        let transfer = || {
+            send(&app, sender, &receiver.address(), 1_000).unwrap();
+        };
+        let receiver_balance = || balance(&app, &receiver.address());
+
+        transfer();
+        app.increase_time(100).unwrap();
+        assert_eq!(receiver_balance(), "100000001000");
+
+        // restoring rewinds balances, height and time
+        app.restore(&snapshot).unwrap();
+        assert_eq!(receiver_balance(), "100000000000");
+        assert_eq!(app.get_block_height(), height);
+        assert_eq!(app.get_block_time_nanos(), block_time_nanos);
+
+        // the account sequence is rewound as well, so the same tx can be sent again
+        transfer();
+        assert_eq!(receiver_balance(), "100000001000");
+
+        // the same snapshot can be restored more than once
+        app.restore(&snapshot).unwrap();
+        assert_eq!(receiver_balance(), "100000000000");
+
+        // but not to another app, not even a fork
+        let fork = app.fork();
+        assert!(matches!(
+            fork.restore(&snapshot),
+            Err(RunnerError::GenericError(_))
+        ));
+        assert!(matches!(
+            CoreumTestApp::new().restore(&snapshot),
+            Err(RunnerError::GenericError(_))
+        ));
+    }
+
+    #[test]
+    fn test_fork() {
+        let app = CoreumTestApp::default();
+        let account = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+
+        let fork = app.fork();
+        assert_eq!(fork.get_block_height(), app.get_block_height());
+        assert_eq!(fork.get_block_time_nanos(), app.get_block_time_nanos());
+
+        // the fork diverges without affecting the original app
+        let validator = app.get_first_validator_signing_account().unwrap();
+        send(&fork, &account, &validator.address(), 1_000).unwrap();
+        fork.increase_time(10).unwrap();
+
+        assert_eq!(balance(&app, &account.address()), "100000000000");
+        assert_ne!(balance(&fork, &account.address()), "100000000000");
+        assert_eq!(fork.get_block_height(), app.get_block_height() + 2);
+
+        // dropping the original app keeps the fork alive
+        drop(app);
+        assert_ne!(balance(&fork, &account.address()), "100000000000");
+    }
+
+    #[test]
+    fn test_export_and_import_genesis() {
+        let app = CoreumTestApp::default();
+        let account = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+
+        let genesis = app.export_genesis().unwrap();
+        assert_eq!(genesis["chain_id"], CHAIN_ID);
+
+        let imported = CoreumTestApp::from_genesis(&genesis).unwrap();
+        assert_eq!(imported.get_block_height(), app.get_block_height() + 1);
+        assert_eq!(balance(&imported, &account.address()), "100000000000");
+
+        // the imported app is fully functional
+        imported
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_import_genesis_with_custom_chain_id() {
+        let app = CoreumTestApp::builder()
+            .chain_id("coreum-exported-1")
+            .build()
+            .unwrap();
+        let account = app
+            .init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+
+        let imported = CoreumTestApp::from_genesis(&app.export_genesis().unwrap()).unwrap();
+
+        // the transaction is signed for the chain id of the genesis
+        let receiver = imported.init_account(&[]).unwrap();
+        send(&imported, &account, &receiver.address(), 1_000).unwrap();
+    }
+
+    #[test]
+    fn test_import_invalid_genesis() {
+        let err = CoreumTestApp::from_genesis(&serde_json::json!({ "chain_id": 1 })).unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+    }
+
+    #[test]
+    fn test_multiple_txs_in_one_block() {
+        let app = CoreumTestApp::default();
+        let accounts = app
+            .init_accounts(&coins(100_000_000_000, FEE_DENOM), 3)
+            .unwrap();
+        let receiver = &accounts[2];
+        let height = app.get_block_height();
+
+        let (results, block_result) = app
+            .block(|block| {
+                accounts[..2]
+                    .iter()
+                    .map(|sender| send(block, sender, &receiver.address(), 1_000))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+
+        assert!(results.iter().all(|res| res.is_ok()));
+        assert!(!block_result.events.is_empty());
+        assert_eq!(app.get_block_height(), height + 1);
+        assert_eq!(balance(&app, &receiver.address()), "100000002000");
+    }
+
+    #[test]
+    fn test_parallel_apps() {
+        use rayon::prelude::*;
+
+        let send_times = |app: &CoreumTestApp, to_address: &str, times: u128| {
+            let sender = app
+                .init_account(&coins(100_000_000_000, FEE_DENOM))
+                .unwrap();
+            for _ in 0..times {
+                send(app, &sender, to_address, 1_000).unwrap();
+            }
+        };
+
+        // every app only sees its own transactions
+        let balances = (1..=8u128)
+            .into_par_iter()
+            .map(|times| {
+                let app = CoreumTestApp::new();
+                let receiver = app.init_account(&[]).unwrap();
+                send_times(&app, &receiver.address(), times);
+                (times, balance(&app, &receiver.address()))
+            })
+            .collect::<Vec<_>>();
+        for (times, balance) in balances {
+            assert_eq!(balance, (times * 1_000).to_string());
+        }
+
+        // the transactions of the threads sharing an app are all executed
+        let app = CoreumTestApp::new();
+        let receiver = app.init_account(&[]).unwrap().address();
+        (0..8)
+            .into_par_iter()
+            .for_each(|_| send_times(&app, &receiver, 2));
+        assert_eq!(balance(&app, &receiver), "16000");
+
+        // the blocks of the threads sharing an app do not interleave
+        let height = app.get_block_height();
+        (0..8).into_par_iter().for_each(|_| {
+            app.block(|_| ()).unwrap();
+            app.increase_time(5).unwrap();
+        });
+        assert_eq!(app.get_block_height(), height + 16);
+    }
+
+    #[test]
+    fn test_block_ended_on_panic() {
+        let app = CoreumTestApp::default();
+        let height = app.get_block_height();
+
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            app.block(|_| panic!("execution failed")).unwrap();
+        }));
+        assert!(panicked.is_err());
+        assert_eq!(app.get_block_height(), height + 1);
+
+        // the app keeps producing blocks
+        app.init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+        assert_eq!(app.get_block_height(), height + 2);
+    }
 }
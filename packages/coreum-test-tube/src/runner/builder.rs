@@ -0,0 +1,243 @@
+use cosmwasm_std::Coin;
+use serde_json::{json, Map, Value};
+use test_tube_coreum::runner::result::RunnerResult;
+use test_tube_coreum::BaseApp;
+
+use crate::runner::app::{CoreumTestApp, DEFAULT_GAS_ADJUSTMENT};
+use crate::runner::network::Network;
+
+/// Builder for a [`CoreumTestApp`] with custom network settings and genesis.
+///
+/// Everything that is not set keeps the value used by [`CoreumTestApp::new`].
+#[derive(Debug, Clone)]
+pub struct CoreumTestAppBuilder {
+    chain_id: String,
+    address_prefix: String,
+    denom: String,
+    gas_adjustment: f64,
+    gov_min_deposit: Option<u128>,
+    gov_voting_period_seconds: Option<u64>,
+    min_self_delegation: Option<u128>,
+    module_genesis: Map<String, Value>,
+    balances: Vec<Value>,
+}
+
+impl Default for CoreumTestAppBuilder {
+    fn default() -> Self {
+        let network = Network::default();
+        Self {
+            chain_id: network.chain_id().to_string(),
+            address_prefix: network.address_prefix().to_string(),
+            denom: network.denom().to_string(),
+            gas_adjustment: DEFAULT_GAS_ADJUSTMENT,
+            gov_min_deposit: None,
+            gov_voting_period_seconds: None,
+            min_self_delegation: None,
+            module_genesis: Map::new(),
+            balances: vec![],
+        }
+    }
+}
+
+impl CoreumTestAppBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the chain id, address prefix and denom of the given network
+    pub fn network(self, network: Network) -> Self {
+        self.chain_id(network.chain_id())
+            .address_prefix(network.address_prefix())
+            .denom(network.denom())
+    }
+
+    /// Set the chain id
+    pub fn chain_id(mut self, chain_id: &str) -> Self {
+        self.chain_id = chain_id.to_string();
+        self
+    }
+
+    /// Set the bech32 address prefix.
+    /// The prefix is global to the Go library, so all apps of a process must use
+    /// the same prefix, building an app with another prefix fails.
+    pub fn address_prefix(mut self, address_prefix: &str) -> Self {
+        self.address_prefix = address_prefix.to_string();
+        self
+    }
+
+    /// Set the native denom, which is also used to pay fees
+    pub fn denom(mut self, denom: &str) -> Self {
+        self.denom = denom.to_string();
+        self
+    }
+
+    /// Set the gas adjustment used by the accounts created by the app
+    pub fn gas_adjustment(mut self, gas_adjustment: f64) -> Self {
+        self.gas_adjustment = gas_adjustment;
+        self
+    }
+
+    /// Set the minimum deposit of gov proposals, in the native denom
+    pub fn gov_min_deposit(mut self, amount: u128) -> Self {
+        self.gov_min_deposit = Some(amount);
+        self
+    }
+
+    /// Set the voting period of gov proposals
+    pub fn gov_voting_period(mut self, seconds: u64) -> Self {
+        self.gov_voting_period_seconds = Some(seconds);
+        self
+    }
+
+    /// Set the minimum self delegation of validators, in the native denom
+    pub fn min_self_delegation(mut self, amount: u128) -> Self {
+        self.min_self_delegation = Some(amount);
+        self
+    }
+
+    /// Override the genesis state of a module, e.g. `assetft`, `dex`, `staking` or `feemodel`.
+    /// The overrides are deep merged into the generated genesis of the module, so only
+    /// the fields to change need to be set, e.g. `{ "tokens": [...] }` for pre-existing tokens.
+    /// Calling it several times for the same module merges the overrides.
+    pub fn module_genesis(mut self, module: &str, genesis: Value) -> Self {
+        let current = self
+            .module_genesis
+            .entry(module.to_string())
+            .or_insert_with(|| json!({}));
+        merge(current, genesis);
+        self
+    }
+
+    /// Override the params of a module, shortcut for `module_genesis(module, { "params": params })`
+    pub fn module_params(self, module: &str, params: Value) -> Self {
+        self.module_genesis(module, json!({ "params": params }))
+    }
+
+    /// Mint the coins to the address at the start of the chain
+    pub fn balance(mut self, address: &str, coins: &[Coin]) -> Self {
+        let mut coins = coins.to_vec();
+
+        // invalid coins if denom are unsorted
+        coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+        self.balances.push(json!({
+            "address": address,
+            "coins": coins,
+        }));
+        self
+    }
+
+    pub fn build(self) -> RunnerResult<CoreumTestApp> {
+        let mut config = json!({
+            "chain_id": self.chain_id,
+            "address_prefix": self.address_prefix,
+            "denom": self.denom,
+            "module_genesis": self.module_genesis,
+            "balances": self.balances,
+        });
+        if let Some(amount) = self.gov_min_deposit {
+            config["gov_min_deposit"] = json!(amount.to_string());
+        }
+        if let Some(seconds) = self.gov_voting_period_seconds {
+            config["gov_voting_period_seconds"] = json!(seconds);
+        }
+        if let Some(amount) = self.min_self_delegation {
+            config["min_self_delegation"] = json!(amount.to_string());
+        }
+
+        Ok(CoreumTestApp {
+            inner: BaseApp::new_with_config(
+                &self.denom,
+                &self.chain_id,
+                &self.address_prefix,
+                self.gas_adjustment,
+                &config,
+            )?,
+        })
+    }
+
+    /// Build the app with its state initialized from the given genesis, e.g. one exported
+    /// with `export_genesis`. The chain id is the one of the genesis, only the address prefix,
+    /// the denom and the gas adjustment of the builder are used.
+    pub fn build_from_genesis(self, genesis: &Value) -> RunnerResult<CoreumTestApp> {
+        Ok(CoreumTestApp {
+            inner: BaseApp::from_genesis(
+                &self.denom,
+                &self.address_prefix,
+                self.gas_adjustment,
+                genesis,
+            )?,
+        })
+    }
+}
+
+fn merge(current: &mut Value, overrides: Value) {
+    match (current, overrides) {
+        (Value::Object(current), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                merge(current.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (current, overrides) => *current = overrides,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use coreum_wasm_sdk::types::coreum::asset::ft::v1::QueryParamsRequest;
+    use coreum_wasm_sdk::types::cosmos::bank::v1beta1::QueryBalanceRequest;
+    use coreum_wasm_sdk::types::cosmos::gov::v1::QueryParamsRequest as GovQueryParamsRequest;
+    use cosmwasm_std::coins;
+    use serde_json::json;
+
+    use crate::runner::app::FEE_DENOM;
+    use crate::{AssetFT, Bank, CoreumTestApp, Gov, Module};
+
+    #[test]
+    fn test_builder() {
+        let address = "core1qyqszqgpqyqszqgpqyqszqgpqyqszqgppae928";
+
+        let app = CoreumTestApp::builder()
+            .chain_id("coreum-custom-1")
+            .gov_min_deposit(5_000)
+            .gov_voting_period(60)
+            .module_params(
+                "assetft",
+                json!({ "issue_fee": { "denom": FEE_DENOM, "amount": "1" } }),
+            )
+            .balance(address, &coins(1_000, FEE_DENOM))
+            .build()
+            .unwrap();
+
+        let assetft_params = AssetFT::new(&app)
+            .query_params(&QueryParamsRequest {})
+            .unwrap()
+            .params
+            .unwrap();
+        assert_eq!(assetft_params.issue_fee.unwrap().amount, "1");
+
+        let gov_params = Gov::new(&app)
+            .query_params(&GovQueryParamsRequest {
+                params_type: "deposit".to_string(),
+            })
+            .unwrap()
+            .params
+            .unwrap();
+        assert_eq!(gov_params.min_deposit[0].amount, "5000");
+        assert_eq!(gov_params.voting_period.unwrap().seconds, 60);
+
+        let balance = Bank::new(&app)
+            .query_balance(&QueryBalanceRequest {
+                address: address.to_string(),
+                denom: FEE_DENOM.to_string(),
+            })
+            .unwrap()
+            .balance
+            .unwrap();
+        assert_eq!(balance.amount, "1000");
+
+        // accounts can transact on the custom chain
+        app.init_account(&coins(100_000_000_000, FEE_DENOM))
+            .unwrap();
+    }
+}
@@ -0,0 +1,280 @@
+use test_tube_coreum::runner::error::RunnerError;
+
+/// Known errors of the Cosmos SDK and the Coreum modules, identified by the ABCI
+/// codespace and code of a failed transaction rather than by its log message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainError {
+    Sdk(SdkError),
+    AssetFt(AssetFtError),
+    Dex(DexError),
+}
+
+/// Errors registered in the `sdk` codespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SdkError {
+    TxDecode,
+    InvalidSequence,
+    Unauthorized,
+    InsufficientFunds,
+    UnknownRequest,
+    InvalidAddress,
+    InvalidPubKey,
+    UnknownAddress,
+    InvalidCoins,
+    OutOfGas,
+    MemoTooLarge,
+    InsufficientFee,
+    TooManySignatures,
+    NoSignatures,
+    InvalidRequest,
+    TxTimeoutHeight,
+    WrongSequence,
+    NotFound,
+    InvalidGasLimit,
+}
+
+/// Errors registered in the `assetft` codespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetFtError {
+    InvalidInput,
+    InvalidDenom,
+    TokenNotFound,
+    FeatureDisabled,
+    WhitelistedLimitExceeded,
+    InvalidState,
+}
+
+/// Errors registered in the `dex` codespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DexError {
+    InvalidInput,
+}
+
+impl ChainError {
+    /// Map the ABCI codespace and code to a known error, `None` for unknown ones.
+    pub fn from_abci(codespace: &str, code: u32) -> Option<Self> {
+        match codespace {
+            "sdk" => SdkError::from_code(code).map(ChainError::Sdk),
+            "assetft" => AssetFtError::from_code(code).map(ChainError::AssetFt),
+            "dex" => DexError::from_code(code).map(ChainError::Dex),
+            _ => None,
+        }
+    }
+
+    /// Map the execute error of a failed transaction to a known error,
+    /// `None` for other errors or unknown codes.
+    pub fn from_runner_error(err: &RunnerError) -> Option<Self> {
+        match err {
+            RunnerError::ExecuteError {
+                code, codespace, ..
+            } => Self::from_abci(codespace, *code),
+            _ => None,
+        }
+    }
+}
+
+impl SdkError {
+    fn from_code(code: u32) -> Option<Self> {
+        Some(match code {
+            2 => SdkError::TxDecode,
+            3 => SdkError::InvalidSequence,
+            4 => SdkError::Unauthorized,
+            5 => SdkError::InsufficientFunds,
+            6 => SdkError::UnknownRequest,
+            7 => SdkError::InvalidAddress,
+            8 => SdkError::InvalidPubKey,
+            9 => SdkError::UnknownAddress,
+            10 => SdkError::InvalidCoins,
+            11 => SdkError::OutOfGas,
+            12 => SdkError::MemoTooLarge,
+            13 => SdkError::InsufficientFee,
+            14 => SdkError::TooManySignatures,
+            15 => SdkError::NoSignatures,
+            18 => SdkError::InvalidRequest,
+            30 => SdkError::TxTimeoutHeight,
+            32 => SdkError::WrongSequence,
+            38 => SdkError::NotFound,
+            41 => SdkError::InvalidGasLimit,
+            _ => return None,
+        })
+    }
+}
+
+impl AssetFtError {
+    fn from_code(code: u32) -> Option<Self> {
+        Some(match code {
+            1 => AssetFtError::InvalidInput,
+            2 => AssetFtError::InvalidDenom,
+            3 => AssetFtError::TokenNotFound,
+            4 => AssetFtError::FeatureDisabled,
+            5 => AssetFtError::WhitelistedLimitExceeded,
+            6 => AssetFtError::InvalidState,
+            _ => return None,
+        })
+    }
+}
+
+impl DexError {
+    fn from_code(code: u32) -> Option<Self> {
+        match code {
+            1 => Some(DexError::InvalidInput),
+            _ => None,
+        }
+    }
+}
+
+impl From<SdkError> for ChainError {
+    fn from(err: SdkError) -> Self {
+        ChainError::Sdk(err)
+    }
+}
+
+impl From<AssetFtError> for ChainError {
+    fn from(err: AssetFtError) -> Self {
+        ChainError::AssetFt(err)
+    }
+}
+
+impl From<DexError> for ChainError {
+    fn from(err: DexError) -> Self {
+        ChainError::Dex(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use coreum_wasm_sdk::types::coreum::asset::ft::v1::{Feature, MsgFreeze, MsgIssue};
+    use coreum_wasm_sdk::types::coreum::dex::v1::{MsgPlaceOrder, OrderType, Side, TimeInForce};
+    use coreum_wasm_sdk::types::cosmos::bank::v1beta1::MsgSend;
+    use coreum_wasm_sdk::types::cosmos::base::v1beta1::Coin as BaseCoin;
+    use cosmwasm_std::Coin;
+
+    use super::{AssetFtError, ChainError, DexError, SdkError};
+    use crate::runner::app::FEE_DENOM;
+    use crate::{Account, AssetFT, Bank, CoreumTestApp, Dex, Module, RunnerError};
+
+    #[test]
+    fn test_from_abci() {
+        assert_eq!(
+            ChainError::from_abci("sdk", 5),
+            Some(ChainError::Sdk(SdkError::InsufficientFunds))
+        );
+        assert_eq!(
+            ChainError::from_abci("assetft", 4),
+            Some(ChainError::AssetFt(AssetFtError::FeatureDisabled))
+        );
+        assert_eq!(ChainError::from_abci("sdk", 1), None);
+        assert_eq!(ChainError::from_abci("unknown", 5), None);
+        assert_eq!(
+            ChainError::from_runner_error(&RunnerError::GenericError("error".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_failed_tx_errors() {
+        let app = CoreumTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, FEE_DENOM)])
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+
+        let bank = Bank::new(&app);
+        let assetft = AssetFT::new(&app);
+        let dex = Dex::new(&app);
+
+        let err = bank
+            .send(
+                MsgSend {
+                    from_address: signer.address(),
+                    to_address: receiver.address(),
+                    amount: vec![BaseCoin {
+                        amount: "200000000000000000000".to_string(),
+                        denom: FEE_DENOM.to_string(),
+                    }],
+                },
+                &signer,
+            )
+            .unwrap_err();
+        let RunnerError::ExecuteError {
+            code,
+            ref codespace,
+            ref msg,
+            ..
+        } = err
+        else {
+            panic!("unexpected error: {:?}", err);
+        };
+        assert_eq!((codespace.as_str(), code), ("sdk", 5));
+        assert!(!msg.is_empty());
+        assert_eq!(
+            ChainError::from_runner_error(&err),
+            Some(SdkError::InsufficientFunds.into())
+        );
+
+        assetft
+            .issue(
+                MsgIssue {
+                    issuer: signer.address(),
+                    symbol: "TEST".to_string(),
+                    subunit: "utest".to_string(),
+                    precision: 6,
+                    initial_amount: "1000".to_string(),
+                    description: String::new(),
+                    features: vec![Feature::Minting as i32],
+                    burn_rate: "0".to_string(),
+                    send_commission_rate: "0".to_string(),
+                    uri: String::new(),
+                    uri_hash: String::new(),
+                    extension_settings: None,
+                    dex_settings: None,
+                },
+                &signer,
+            )
+            .unwrap();
+        let denom = format!("{}-{}", "utest", signer.address()).to_lowercase();
+
+        let err = assetft
+            .freeze(
+                MsgFreeze {
+                    sender: signer.address(),
+                    account: receiver.address(),
+                    coin: Some(BaseCoin {
+                        amount: "10".to_string(),
+                        denom: denom.clone(),
+                    }),
+                },
+                &signer,
+            )
+            .unwrap_err();
+        assert_eq!(
+            ChainError::from_runner_error(&err),
+            Some(AssetFtError::FeatureDisabled.into())
+        );
+        if let RunnerError::ExecuteError { gas_used, .. } = err {
+            assert!(gas_used > 0);
+        }
+
+        let err = dex
+            .place_order(
+                MsgPlaceOrder {
+                    sender: signer.address(),
+                    r#type: OrderType::Limit as i32,
+                    id: "id".to_string(),
+                    base_denom: denom,
+                    quote_denom: FEE_DENOM.to_string(),
+                    price: "1".to_string(),
+                    quantity: "0".to_string(),
+                    side: Side::Sell as i32,
+                    good_til: None,
+                    time_in_force: TimeInForce::Gtc as i32,
+                },
+                &signer,
+            )
+            .unwrap_err();
+        assert_eq!(
+            ChainError::from_runner_error(&err),
+            Some(DexError::InvalidInput.into())
+        );
+    }
+}
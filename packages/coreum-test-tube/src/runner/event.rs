@@ -0,0 +1,172 @@
+use coreum_wasm_sdk::types::coreum::asset::ft::v1::{
+    EventFrozenAmountChanged, EventIssued, Feature,
+};
+use coreum_wasm_sdk::types::coreum::dex::v1::{EventOrderPlaced, EventOrderReduced};
+use cosmwasm_std::Event;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use test_tube_coreum::runner::error::{DecodeError, RunnerError};
+use test_tube_coreum::runner::result::{ExecuteMultiResponse, ExecuteResponse, RunnerResult};
+
+/// Typed event emitted by a module, with the proto message JSON encoded in the event attributes,
+/// one attribute per field.
+pub trait TypedEvent: DeserializeOwned {
+    /// Proto full name of the event, which is the type of the emitted event.
+    const EVENT_TYPE: &'static str;
+
+    /// Decode the event from its fields, e.g. to convert enum names to their values.
+    fn from_fields(fields: Map<String, Value>) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(Value::Object(fields))
+    }
+}
+
+impl TypedEvent for EventIssued {
+    const EVENT_TYPE: &'static str = "coreum.asset.ft.v1.EventIssued";
+
+    fn from_fields(mut fields: Map<String, Value>) -> Result<Self, serde_json::Error> {
+        // features are encoded by their names, the prost type holds their values
+        if let Some(Value::Array(features)) = fields.get_mut("features") {
+            for feature in features.iter_mut() {
+                if let Some(value) = feature.as_str().and_then(Feature::from_str_name) {
+                    *feature = Value::from(value as i32);
+                }
+            }
+        }
+        serde_json::from_value(Value::Object(fields))
+    }
+}
+
+impl TypedEvent for EventFrozenAmountChanged {
+    const EVENT_TYPE: &'static str = "coreum.asset.ft.v1.EventFrozenAmountChanged";
+}
+
+impl TypedEvent for EventOrderPlaced {
+    const EVENT_TYPE: &'static str = "coreum.dex.v1.EventOrderPlaced";
+}
+
+impl TypedEvent for EventOrderReduced {
+    const EVENT_TYPE: &'static str = "coreum.dex.v1.EventOrderReduced";
+}
+
+/// Decoding of the typed events emitted by a transaction.
+pub trait TypedEvents {
+    /// Decode all events of type `T`, in the order they were emitted.
+    fn typed_events<T: TypedEvent>(&self) -> RunnerResult<Vec<T>>;
+}
+
+impl<R> TypedEvents for ExecuteResponse<R>
+where
+    R: prost::Message + Default,
+{
+    fn typed_events<T: TypedEvent>(&self) -> RunnerResult<Vec<T>> {
+        decode_typed_events(&self.events)
+    }
+}
+
+impl TypedEvents for ExecuteMultiResponse {
+    fn typed_events<T: TypedEvent>(&self) -> RunnerResult<Vec<T>> {
+        decode_typed_events(&self.events)
+    }
+}
+
+fn decode_typed_events<T: TypedEvent>(events: &[Event]) -> RunnerResult<Vec<T>> {
+    events
+        .iter()
+        .filter(|e| e.ty == T::EVENT_TYPE)
+        .map(|e| {
+            let fields = e
+                .attributes
+                .iter()
+                // added by the SDK to every event of a message, not a field of the event
+                .filter(|a| a.key != "msg_index")
+                .map(|a| {
+                    let value = serde_json::from_str(&a.value)
+                        .unwrap_or_else(|_| Value::String(a.value.clone()));
+                    (a.key.clone(), value)
+                })
+                .collect();
+
+            T::from_fields(fields)
+                .map_err(DecodeError::JsonDecodeError)
+                .map_err(RunnerError::DecodeError)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use coreum_wasm_sdk::types::coreum::asset::ft::v1::{
+        EventFrozenAmountChanged, EventIssued, Feature, MsgFreeze, MsgIssue,
+    };
+    use coreum_wasm_sdk::types::cosmos::base::v1beta1::Coin as BaseCoin;
+    use cosmwasm_std::Coin;
+
+    use super::TypedEvents;
+    use crate::runner::app::FEE_DENOM;
+    use crate::{Account, AssetFT, CoreumTestApp, Module};
+
+    #[test]
+    fn test_asset_ft_typed_events() {
+        let app = CoreumTestApp::new();
+        let issuer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, FEE_DENOM)])
+            .unwrap();
+        let holder = app.init_account(&[]).unwrap();
+
+        let assetft = AssetFT::new(&app);
+
+        let res = assetft
+            .issue(
+                MsgIssue {
+                    issuer: issuer.address(),
+                    symbol: "TEST".to_string(),
+                    subunit: "utest".to_string(),
+                    precision: 6,
+                    initial_amount: "1000".to_string(),
+                    description: "test_description".to_string(),
+                    features: vec![Feature::Freezing as i32],
+                    burn_rate: "0".to_string(),
+                    send_commission_rate: "0".to_string(),
+                    uri: String::new(),
+                    uri_hash: String::new(),
+                    extension_settings: None,
+                    dex_settings: None,
+                },
+                &issuer,
+            )
+            .unwrap();
+        let denom = format!("{}-{}", "utest", issuer.address()).to_lowercase();
+
+        let issued = res.typed_events::<EventIssued>().unwrap();
+        assert_eq!(issued.len(), 1);
+        assert_eq!(issued[0].denom, denom);
+        assert_eq!(issued[0].issuer, issuer.address());
+        assert_eq!(issued[0].initial_amount, "1000");
+        assert_eq!(issued[0].features, vec![Feature::Freezing as i32]);
+        assert!(res
+            .typed_events::<EventFrozenAmountChanged>()
+            .unwrap()
+            .is_empty());
+
+        let res = assetft
+            .freeze(
+                MsgFreeze {
+                    sender: issuer.address(),
+                    account: holder.address(),
+                    coin: Some(BaseCoin {
+                        amount: "10".to_string(),
+                        denom: denom.clone(),
+                    }),
+                },
+                &issuer,
+            )
+            .unwrap();
+
+        let frozen = res.typed_events::<EventFrozenAmountChanged>().unwrap();
+        assert_eq!(frozen.len(), 1);
+        assert_eq!(frozen[0].account, holder.address());
+        assert_eq!(frozen[0].denom, denom);
+        assert_eq!(frozen[0].previous_amount, "0");
+        assert_eq!(frozen[0].current_amount, "10");
+    }
+}
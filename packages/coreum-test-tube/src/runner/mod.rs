@@ -1 +1,5 @@
 pub mod app;
+pub mod builder;
+pub mod error;
+pub mod event;
+pub mod network;
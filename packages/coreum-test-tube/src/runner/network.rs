@@ -0,0 +1,58 @@
+use crate::runner::app::{ADDRESS_PREFIX, CHAIN_ID, FEE_DENOM};
+
+/// Coreum networks, each with its own chain id, bech32 address prefix and native denom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Testnet,
+    Devnet,
+}
+
+impl Network {
+    pub fn chain_id(&self) -> &'static str {
+        match self {
+            Network::Mainnet => CHAIN_ID,
+            Network::Testnet => "coreum-testnet-1",
+            Network::Devnet => "coreum-devnet-1",
+        }
+    }
+
+    pub fn address_prefix(&self) -> &'static str {
+        match self {
+            Network::Mainnet => ADDRESS_PREFIX,
+            Network::Testnet => "testcore",
+            Network::Devnet => "devcore",
+        }
+    }
+
+    pub fn denom(&self) -> &'static str {
+        match self {
+            Network::Mainnet => FEE_DENOM,
+            Network::Testnet => "utestcore",
+            Network::Devnet => "udevcore",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Network;
+
+    #[test]
+    fn test_network_presets() {
+        assert_eq!(Network::default(), Network::Mainnet);
+
+        assert_eq!(Network::Mainnet.chain_id(), "coreum-mainnet-1");
+        assert_eq!(Network::Mainnet.address_prefix(), "core");
+        assert_eq!(Network::Mainnet.denom(), "ucore");
+
+        assert_eq!(Network::Testnet.chain_id(), "coreum-testnet-1");
+        assert_eq!(Network::Testnet.address_prefix(), "testcore");
+        assert_eq!(Network::Testnet.denom(), "utestcore");
+
+        assert_eq!(Network::Devnet.chain_id(), "coreum-devnet-1");
+        assert_eq!(Network::Devnet.address_prefix(), "devcore");
+        assert_eq!(Network::Devnet.denom(), "udevcore");
+    }
+}
@@ -0,0 +1,21 @@
+//! The address prefix is global to the Go library, so these tests run in their own process.
+
+use coreum_test_tube::{CoreumTestApp, Network, RunnerError};
+
+#[test]
+fn test_one_address_prefix_per_process() {
+    let _app = CoreumTestApp::new_with_network(Network::Devnet).unwrap();
+
+    let err = CoreumTestApp::new_with_network(Network::Testnet).unwrap_err();
+    let RunnerError::ExecuteError { msg, .. } = err else {
+        panic!("unexpected error: {:?}", err);
+    };
+    assert!(msg.contains("only one prefix is supported per process"));
+
+    // other chains with the same prefix are fine
+    CoreumTestApp::builder()
+        .network(Network::Devnet)
+        .chain_id("coreum-devnet-2")
+        .build()
+        .unwrap();
+}
@@ -0,0 +1,40 @@
+use coreum_test_tube::coreum_wasm_sdk::types::cosmos::bank::v1beta1::{
+    MsgSend, QueryBalanceRequest,
+};
+use coreum_test_tube::coreum_wasm_sdk::types::cosmos::base::v1beta1::Coin as BaseCoin;
+use coreum_test_tube::{Account, Bank, CoreumTestApp, Module, Network};
+use cosmwasm_std::coins;
+
+/// Send funds between two accounts of an app built for the network.
+pub fn test_send(network: Network) {
+    let app = CoreumTestApp::new_with_network(network).unwrap();
+    let sender = app
+        .init_account(&coins(100_000_000_000, network.denom()))
+        .unwrap();
+    let receiver = app.init_account(&[]).unwrap();
+    assert!(sender.address().starts_with(network.address_prefix()));
+
+    let bank = Bank::new(&app);
+    bank.send(
+        MsgSend {
+            from_address: sender.address(),
+            to_address: receiver.address(),
+            amount: vec![BaseCoin {
+                amount: 1_000u128.to_string(),
+                denom: network.denom().to_string(),
+            }],
+        },
+        &sender,
+    )
+    .unwrap();
+
+    let balance = bank
+        .query_balance(&QueryBalanceRequest {
+            address: receiver.address(),
+            denom: network.denom().to_string(),
+        })
+        .unwrap()
+        .balance
+        .unwrap();
+    assert_eq!(balance.amount, "1000");
+}
@@ -0,0 +1,10 @@
+//! The address prefix is global to the Go library, so the network runs in its own process.
+
+mod common;
+
+use coreum_test_tube::Network;
+
+#[test]
+fn test_devnet_send() {
+    common::test_send(Network::Devnet);
+}
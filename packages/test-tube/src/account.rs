@@ -1,8 +1,17 @@
+use cosmrs::proto::cosmos::crypto::multisig::v1beta1::{CompactBitArray, MultiSignature};
+use cosmrs::proto::cosmos::crypto::multisig::LegacyAminoPubKey;
+use cosmrs::proto::cosmos::tx::signing::v1beta1::SignMode as ProtoSignMode;
+use cosmrs::proto::cosmos::tx::v1beta1::{mode_info, ModeInfo, SignerInfo as ProtoSignerInfo};
 use cosmrs::{
     crypto::{secp256k1::SigningKey, PublicKey},
-    AccountId,
+    AccountId, ErrorReport,
 };
 use cosmwasm_std::Coin;
+use prost::Message;
+use sha2::{Digest, Sha256};
+
+use crate::runner::error::RunnerError;
+use crate::runner::result::RunnerResult;
 
 pub trait Account {
     fn public_key(&self) -> PublicKey;
@@ -16,27 +25,58 @@ pub trait Account {
             .expect("Prefix is constant and must valid")
     }
 }
+/// Produces the signatures of the transactions, e.g. a key held in memory or an external
+/// signing service such as an HSM or KMS.
+pub trait Signer: Send + Sync {
+    fn public_key(&self) -> PublicKey;
+    /// Sign the bytes, returning the 64 bytes `r || s` secp256k1 signature.
+    fn sign(&self, sign_bytes: &[u8]) -> Result<Vec<u8>, ErrorReport>;
+    /// The in memory key of the signer, if any.
+    fn signing_key(&self) -> Option<&SigningKey> {
+        None
+    }
+}
+
+impl Signer for SigningKey {
+    fn public_key(&self) -> PublicKey {
+        SigningKey::public_key(self)
+    }
+
+    fn sign(&self, sign_bytes: &[u8]) -> Result<Vec<u8>, ErrorReport> {
+        Ok(SigningKey::sign(self, sign_bytes)?.to_vec())
+    }
+
+    fn signing_key(&self) -> Option<&SigningKey> {
+        Some(self)
+    }
+}
+
 pub struct SigningAccount {
     prefix: String,
-    signing_key: SigningKey,
+    signer: Box<dyn Signer>,
     fee_setting: FeeSetting,
 }
 
 impl SigningAccount {
     pub fn new(prefix: String, signing_key: SigningKey, fee_setting: FeeSetting) -> Self {
+        Self::from_signer(prefix, signing_key, fee_setting)
+    }
+
+    /// Create an account signing with the given signer instead of an in memory key.
+    pub fn from_signer(
+        prefix: String,
+        signer: impl Signer + 'static,
+        fee_setting: FeeSetting,
+    ) -> Self {
         SigningAccount {
             prefix,
-            signing_key,
+            signer: Box::new(signer),
             fee_setting,
         }
     }
 
     pub fn with_prefix(self, prefix: String) -> Self {
-        Self {
-            prefix,
-            signing_key: self.signing_key,
-            fee_setting: self.fee_setting,
-        }
+        Self { prefix, ..self }
     }
 
     pub fn fee_setting(&self) -> &FeeSetting {
@@ -45,16 +85,15 @@ impl SigningAccount {
 
     pub fn with_fee_setting(self, fee_setting: FeeSetting) -> Self {
         Self {
-            prefix: self.prefix,
-            signing_key: self.signing_key,
             fee_setting,
+            ..self
         }
     }
 }
 
 impl Account for SigningAccount {
     fn public_key(&self) -> PublicKey {
-        self.signing_key.public_key()
+        self.signer.public_key()
     }
 
     fn prefix(&self) -> &str {
@@ -63,8 +102,20 @@ impl Account for SigningAccount {
 }
 
 impl SigningAccount {
+    pub fn signer(&self) -> &dyn Signer {
+        self.signer.as_ref()
+    }
+
+    /// The in memory key of the account.
+    ///
+    /// # Panics
+    ///
+    /// If the account was created with [`Self::from_signer`] from a signer holding no key,
+    /// use [`Self::signer`] for such accounts.
     pub fn signing_key(&'_ self) -> &'_ SigningKey {
-        &self.signing_key
+        self.signer
+            .signing_key()
+            .expect("account is signed by an external signer holding no key")
     }
 }
 
@@ -125,3 +176,181 @@ pub enum FeeSetting {
         gas_limit: u64,
     },
 }
+
+/// Account controlled by a `LegacyAminoMultisig` threshold key made of the keys of its members.
+/// Transactions are signed by the signing members only, which must reach the threshold
+/// for the transaction to be valid.
+pub struct MultisigAccount {
+    prefix: String,
+    threshold: u32,
+    members: Vec<SigningAccount>,
+    signing_members: Vec<usize>,
+    fee_setting: FeeSetting,
+}
+
+impl MultisigAccount {
+    /// Create a multisig account from its members, in the order of the multisig public keys.
+    /// The first `threshold` members sign transactions by default.
+    pub fn new(
+        prefix: String,
+        threshold: u32,
+        members: Vec<SigningAccount>,
+        fee_setting: FeeSetting,
+    ) -> Self {
+        MultisigAccount {
+            prefix,
+            threshold,
+            signing_members: (0..(threshold as usize).min(members.len())).collect(),
+            members,
+            fee_setting,
+        }
+    }
+
+    /// Set the indexes of the members signing transactions, which must be members of the multisig.
+    pub fn with_signing_members(self, mut signing_members: Vec<usize>) -> RunnerResult<Self> {
+        if let Some(i) = signing_members.iter().find(|&&i| i >= self.members.len()) {
+            return Err(RunnerError::GenericError(format!(
+                "multisig has no member at index {}",
+                i
+            )));
+        }
+
+        signing_members.sort_unstable();
+        signing_members.dedup();
+
+        Ok(Self {
+            signing_members,
+            ..self
+        })
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    pub fn members(&self) -> &[SigningAccount] {
+        &self.members
+    }
+
+    pub fn fee_setting(&self) -> &FeeSetting {
+        &self.fee_setting
+    }
+
+    pub fn with_fee_setting(self, fee_setting: FeeSetting) -> Self {
+        Self {
+            fee_setting,
+            ..self
+        }
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Multisig public key as `/cosmos.crypto.multisig.LegacyAminoPubKey`.
+    pub fn multisig_public_key(&self) -> cosmrs::Any {
+        let public_key = LegacyAminoPubKey {
+            threshold: self.threshold,
+            public_keys: self
+                .members
+                .iter()
+                .map(|member| {
+                    member
+                        .public_key()
+                        .to_any()
+                        .expect("secp256k1 public key must be encodable")
+                })
+                .collect(),
+        };
+
+        cosmrs::Any {
+            type_url: "/cosmos.crypto.multisig.LegacyAminoPubKey".to_string(),
+            value: public_key.encode_to_vec(),
+        }
+    }
+
+    /// Address of the multisig, the hash of its amino encoded public key.
+    pub fn account_id(&self) -> AccountId {
+        let mut amino_bytes = MULTISIG_AMINO_PREFIX.to_vec();
+        amino_bytes.push(0x08);
+        encode_uvarint(self.threshold as u64, &mut amino_bytes);
+
+        for member in &self.members {
+            let key = member.public_key().to_bytes();
+            amino_bytes.push(0x12);
+            encode_uvarint(
+                (SECP256K1_AMINO_PREFIX.len() + 1 + key.len()) as u64,
+                &mut amino_bytes,
+            );
+            amino_bytes.extend_from_slice(&SECP256K1_AMINO_PREFIX);
+            amino_bytes.push(key.len() as u8);
+            amino_bytes.extend_from_slice(&key);
+        }
+
+        AccountId::new(&self.prefix, &Sha256::digest(&amino_bytes)[..20])
+            .expect("Prefix is constant and must valid")
+    }
+
+    pub fn address(&self) -> String {
+        self.account_id().to_string()
+    }
+
+    /// Signer info with one mode info for each signing member.
+    pub(crate) fn signer_info(&self, sequence: u64, sign_mode: ProtoSignMode) -> ProtoSignerInfo {
+        let mut elems = vec![0u8; self.members.len().div_ceil(8)];
+        for i in &self.signing_members {
+            elems[i / 8] |= 1 << (7 - i % 8);
+        }
+
+        ProtoSignerInfo {
+            public_key: Some(self.multisig_public_key()),
+            mode_info: Some(ModeInfo {
+                sum: Some(mode_info::Sum::Multi(mode_info::Multi {
+                    bitarray: Some(CompactBitArray {
+                        extra_bits_stored: (self.members.len() % 8) as u32,
+                        elems,
+                    }),
+                    mode_infos: self
+                        .signing_members
+                        .iter()
+                        .map(|_| ModeInfo {
+                            sum: Some(mode_info::Sum::Single(mode_info::Single {
+                                mode: sign_mode as i32,
+                            })),
+                        })
+                        .collect(),
+                })),
+            }),
+            sequence,
+        }
+    }
+
+    /// Collect the signatures of the signing members into a `MultiSignature`.
+    pub(crate) fn sign(&self, sign_doc_bytes: &[u8]) -> Result<Vec<u8>, ErrorReport> {
+        let signatures = self
+            .signing_members
+            .iter()
+            .map(|&i| {
+                let member = self.members.get(i).ok_or_else(|| {
+                    ErrorReport::msg(format!("multisig has no member at index {}", i))
+                })?;
+                member.signer().sign(sign_doc_bytes)
+            })
+            .collect::<Result<Vec<_>, ErrorReport>>()?;
+
+        Ok(MultiSignature { signatures }.encode_to_vec())
+    }
+}
+
+/// Amino prefix of `tendermint/PubKeyMultisigThreshold`.
+const MULTISIG_AMINO_PREFIX: [u8; 4] = [0x22, 0xc1, 0xf7, 0xe2];
+/// Amino prefix of `tendermint/PubKeySecp256k1`.
+const SECP256K1_AMINO_PREFIX: [u8; 4] = [0xeb, 0x5a, 0xe9, 0x87];
+
+fn encode_uvarint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
@@ -211,9 +211,29 @@ fn bindgen_test_layout_GoSlice() {
 extern "C" {
     pub fn InitTestEnv() -> GoUint64;
 }
+extern "C" {
+    pub fn InitTestEnvWithConfig(configJson: GoString) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn InitTestEnvFromGenesis(
+        genesisJson: GoString,
+        addressPrefix: GoString,
+        denom: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn ForkTestEnv(envId: GoUint64) -> GoUint64;
+}
 extern "C" {
     pub fn InitAccount(envId: GoUint64, coinsJson: GoString) -> *mut ::std::os::raw::c_char;
 }
+extern "C" {
+    pub fn FundAccount(
+        envId: GoUint64,
+        bech32Address: GoString,
+        coinsJson: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
 extern "C" {
     pub fn BeginBlock(envId: GoUint64);
 }
@@ -221,11 +241,34 @@ extern "C" {
     pub fn EndBlock(envId: GoUint64);
 }
 extern "C" {
-    pub fn IncreaseTime(envId: GoUint64, seconds: GoInt64);
+    pub fn EndBlockWithResult(envId: GoUint64) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn IncreaseTime(envId: GoUint64, seconds: GoInt64) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn SetBlockTime(envId: GoUint64, nanos: GoInt64) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn AdvanceBlocks(
+        envId: GoUint64,
+        blocks: GoUint64,
+        secondsPerBlock: GoUint64,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn AdvanceToHeight(
+        envId: GoUint64,
+        height: GoInt64,
+        secondsPerBlock: GoUint64,
+    ) -> *mut ::std::os::raw::c_char;
 }
 extern "C" {
     pub fn Execute(envId: GoUint64, base64ReqDeliverTx: GoString) -> *mut ::std::os::raw::c_char;
 }
+extern "C" {
+    pub fn CheckTx(envId: GoUint64, base64Tx: GoString) -> *mut ::std::os::raw::c_char;
+}
 extern "C" {
     pub fn Query(
         envId: GoUint64,
@@ -234,10 +277,21 @@ extern "C" {
     ) -> *mut ::std::os::raw::c_char;
 }
 extern "C" {
-    pub fn AccountSequence(envId: GoUint64, bech32Address: GoString) -> GoUint64;
+    pub fn AccountSequence(envId: GoUint64, bech32Address: GoString)
+        -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn AccountNumber(envId: GoUint64, bech32Address: GoString) -> *mut ::std::os::raw::c_char;
 }
 extern "C" {
-    pub fn AccountNumber(envId: GoUint64, bech32Address: GoString) -> GoUint64;
+    pub fn GetSignBytes(
+        envId: GoUint64,
+        signMode: GoInt32,
+        base64TxBytes: GoString,
+        signerIndex: GoInt,
+        accountNumber: GoUint64,
+        sequence: GoUint64,
+    ) -> *mut ::std::os::raw::c_char;
 }
 extern "C" {
     pub fn Simulate(envId: GoUint64, base64TxBytes: GoString) -> *mut ::std::os::raw::c_char;
@@ -262,12 +316,30 @@ extern "C" {
 extern "C" {
     pub fn GetValidatorPrivateKey(envId: GoUint64, n: GoInt32) -> *mut ::std::os::raw::c_char;
 }
+extern "C" {
+    pub fn QueryTx(envId: GoUint64, hash: GoString) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn SearchTxs(envId: GoUint64, query: GoString) -> *mut ::std::os::raw::c_char;
+}
 extern "C" {
     pub fn GetBlockTime(envId: GoUint64) -> GoInt64;
 }
 extern "C" {
     pub fn GetBlockHeight(envId: GoUint64) -> GoInt64;
 }
+extern "C" {
+    pub fn ExportGenesis(envId: GoUint64) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn TakeSnapshot(envId: GoUint64) -> GoUint64;
+}
+extern "C" {
+    pub fn RestoreSnapshot(envId: GoUint64, snapshotId: GoUint64) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn ReleaseSnapshot(snapshotId: GoUint64);
+}
 
 extern "C" {
     pub fn CleanUp(envId: GoUint64);
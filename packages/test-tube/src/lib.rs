@@ -9,9 +9,21 @@ pub mod utils;
 
 pub use cosmrs;
 
-pub use account::{Account, FeeSetting, NonSigningAccount, SigningAccount};
+pub use account::{
+    Account, FeeSetting, MultisigAccount, NonSigningAccount, Signer, SigningAccount,
+};
 pub use module::*;
 pub use runner::app::BaseApp;
+pub use runner::block::Block;
 pub use runner::error::{DecodeError, EncodeError, RunnerError};
-pub use runner::result::{ExecuteResponse, RunnerExecuteResult, RunnerResult};
+pub use runner::event::{EventMatcher, EventQuery, Events};
+pub use runner::gas::{
+    GasRecord, GasRecordKind, GasRecorder, GasRegression, GasReport, GasReportEntry,
+};
+pub use runner::options::{SignMode, TxOptions};
+pub use runner::result::{
+    BlockResult, CheckTxResult, ExecuteMultiResponse, ExecuteResponse, RunnerExecuteResult,
+    RunnerResult,
+};
+pub use runner::snapshot::Snapshot;
 pub use runner::Runner;
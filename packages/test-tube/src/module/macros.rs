@@ -51,3 +51,36 @@ macro_rules! fn_query {
         }
     };
 }
+
+/// Assert that the events have one of the given type with all the given attributes, e.g.
+/// `assert_event!(res, "transfer", "recipient" => addr, "amount" => "100ucore")`.
+#[macro_export]
+macro_rules! assert_event {
+    ($events:expr, $ty:expr $(, $key:expr => $value:expr)*) => {{
+        let matcher = $crate::runner::event::EventMatcher::new($ty)$(.with_attr($key, $value))*;
+        let events = &$events;
+        let events = $crate::runner::event::Events::events(events);
+        assert!(
+            $crate::runner::event::Events::has_event(events, &matcher),
+            "no event matching {:?} in {:#?}",
+            matcher,
+            events
+        );
+    }};
+}
+
+/// Assert that the events have none of the given type with all the given attributes.
+#[macro_export]
+macro_rules! assert_no_event {
+    ($events:expr, $ty:expr $(, $key:expr => $value:expr)*) => {{
+        let matcher = $crate::runner::event::EventMatcher::new($ty)$(.with_attr($key, $value))*;
+        let events = &$events;
+        let events = $crate::runner::event::Events::events(events);
+        assert!(
+            !$crate::runner::event::Events::has_event(events, &matcher),
+            "unexpected event matching {:?} in {:#?}",
+            matcher,
+            events
+        );
+    }};
+}
@@ -1,37 +1,71 @@
 use std::ffi::CString;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
+use bip32::{DerivationPath, Language, Mnemonic, XPrv};
 use cosmrs::crypto::secp256k1::SigningKey;
-use cosmrs::proto::cosmos::base::abci::v1beta1::SimulationResponse;
-use cosmrs::tx::{Fee, SignerInfo};
+use cosmrs::proto::cosmos::base::abci::v1beta1::{SearchTxsResult, SimulationResponse, TxResponse};
+use cosmrs::proto::cosmos::tx::signing::v1beta1::SignMode as ProtoSignMode;
+use cosmrs::proto::cosmos::tx::v1beta1::{
+    mode_info, AuthInfo, ModeInfo, SignDoc, SignerInfo as ProtoSignerInfo, TxRaw,
+};
+use cosmrs::proto::tendermint::v0_38::abci::{ResponseCheckTx, ResponseFinalizeBlock};
+use cosmrs::tx::Fee;
 use cosmrs::{tx, Any};
 use cosmwasm_std::{Coin, Timestamp};
 use prost::Message;
+use sha2::{Digest, Sha256};
 
-use crate::account::{Account, FeeSetting, SigningAccount};
+use crate::account::{Account, FeeSetting, MultisigAccount, SigningAccount};
 use crate::bindings::{
-    AccountNumber, AccountSequence, BeginBlock, CleanUp, EndBlock, Execute, GetBlockHeight,
-    GetBlockTime, GetParamSet, GetValidatorAddress, GetValidatorPrivateKey, IncreaseTime,
-    InitAccount, InitTestEnv, Query, SetParamSet, Simulate,
+    AccountNumber, AccountSequence, AdvanceBlocks, AdvanceToHeight, BeginBlock, CheckTx, CleanUp,
+    EndBlock, EndBlockWithResult, Execute, ExportGenesis, ForkTestEnv, FundAccount, GetBlockHeight,
+    GetBlockTime, GetParamSet, GetSignBytes, GetValidatorAddress, GetValidatorPrivateKey,
+    IncreaseTime, InitAccount, InitTestEnv, InitTestEnvFromGenesis, InitTestEnvWithConfig, Query,
+    QueryTx, RestoreSnapshot, SearchTxs, SetBlockTime, SetParamSet, Simulate, TakeSnapshot,
 };
 use crate::redefine_as_go_string;
+use crate::runner::block::Block;
 use crate::runner::error::{DecodeError, EncodeError, RunnerError};
+use crate::runner::event::EventQuery;
+use crate::runner::gas::{GasRecordKind, GasRecorder};
+use crate::runner::options::{SignMode, TxOptions};
 use crate::runner::result::RawResult;
-use crate::runner::result::{RunnerExecuteResult, RunnerResult};
+use crate::runner::result::{
+    BlockResult, CheckTxResult, ExecuteResponse, RunnerExecuteResult, RunnerResult,
+};
+use crate::runner::snapshot::Snapshot;
 use crate::runner::Runner;
 
 pub const MIN_GAS_PRICE: u128 = 2_500;
 
-#[derive(Debug, PartialEq)]
+/// App running on an env of the Go library. The library locks the env on every call and
+/// the app holds its block lock from beginning to ending each block it produces,
+/// so the app can be shared across threads and different apps run in parallel.
+#[derive(Debug)]
 pub struct BaseApp {
     id: u64,
     fee_denom: String,
     chain_id: String,
     address_prefix: String,
     default_gas_adjustment: f64,
+    gas_recorder: GasRecorder,
+    block_lock: Mutex<()>,
+}
+
+/// Apps are equal if they run on the same env.
+impl PartialEq for BaseApp {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
 }
 
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<BaseApp>();
+};
+
 impl BaseApp {
     pub fn new(
         fee_denom: &str,
@@ -46,13 +80,193 @@ impl BaseApp {
             chain_id: chain_id.to_string(),
             address_prefix: address_prefix.to_string(),
             default_gas_adjustment,
+            gas_recorder: GasRecorder::default(),
+            block_lock: Mutex::default(),
+        }
+    }
+
+    /// Create a new app with the environment built from the given config.
+    /// The config is interpreted by the chain specific library, fields that are
+    /// not set keep their default values.
+    pub fn new_with_config(
+        fee_denom: &str,
+        chain_id: &str,
+        address_prefix: &str,
+        default_gas_adjustment: f64,
+        config: &serde_json::Value,
+    ) -> RunnerResult<Self> {
+        let config_json = serde_json::to_string(config).map_err(EncodeError::JsonEncodeError)?;
+        redefine_as_go_string!(config_json);
+
+        let id = unsafe {
+            let res = InitTestEnvWithConfig(config_json);
+            RawResult::from_non_null_ptr(res).into_result()?
+        };
+
+        Ok(BaseApp {
+            id: decode_u64(id),
+            fee_denom: fee_denom.to_string(),
+            chain_id: chain_id.to_string(),
+            address_prefix: address_prefix.to_string(),
+            default_gas_adjustment,
+            gas_recorder: GasRecorder::default(),
+            block_lock: Mutex::default(),
+        })
+    }
+
+    /// Create a new app with its state initialized from the given genesis,
+    /// for example one produced by [`BaseApp::export_genesis`].
+    ///
+    /// The transactions are signed for the `chain_id` of the genesis.
+    /// Private keys of the genesis validators are not part of the genesis,
+    /// so the validator signing account is not available for such app.
+    pub fn from_genesis(
+        fee_denom: &str,
+        address_prefix: &str,
+        default_gas_adjustment: f64,
+        genesis: &serde_json::Value,
+    ) -> RunnerResult<Self> {
+        // an invalid chain id is rejected by the chain when the genesis is loaded
+        let chain_id = genesis["chain_id"].as_str().unwrap_or_default().to_string();
+
+        let genesis_json = serde_json::to_string(genesis).map_err(EncodeError::JsonEncodeError)?;
+
+        let id = {
+            let (prefix, denom) = (address_prefix, fee_denom);
+            redefine_as_go_string!(genesis_json, prefix, denom);
+
+            unsafe {
+                let res = InitTestEnvFromGenesis(genesis_json, prefix, denom);
+                RawResult::from_non_null_ptr(res).into_result()?
+            }
+        };
+
+        Ok(BaseApp {
+            id: decode_u64(id),
+            fee_denom: fee_denom.to_string(),
+            chain_id,
+            address_prefix: address_prefix.to_string(),
+            default_gas_adjustment,
+            gas_recorder: GasRecorder::default(),
+            block_lock: Mutex::default(),
+        })
+    }
+
+    /// Export the current chain state as a genesis, which can be used
+    /// to start a new app with [`BaseApp::from_genesis`].
+    pub fn export_genesis(&self) -> RunnerResult<serde_json::Value> {
+        let _lock = self.lock_block();
+        let genesis = unsafe {
+            let res = ExportGenesis(self.id);
+            RawResult::from_non_null_ptr(res).into_result()?
+        };
+
+        serde_json::from_slice(&genesis)
+            .map_err(DecodeError::JsonDecodeError)
+            .map_err(RunnerError::DecodeError)
+    }
+
+    /// Create an independent copy of this app with the same chain state.
+    /// The fork has its own lifecycle and is cleaned up when dropped.
+    pub fn fork(&self) -> Self {
+        let id = {
+            let _lock = self.lock_block();
+            unsafe { ForkTestEnv(self.id) }
+        };
+        BaseApp {
+            id,
+            fee_denom: self.fee_denom.clone(),
+            chain_id: self.chain_id.clone(),
+            address_prefix: self.address_prefix.clone(),
+            default_gas_adjustment: self.default_gas_adjustment,
+            gas_recorder: GasRecorder::default(),
+            block_lock: Mutex::default(),
         }
     }
 
-    /// Increase the time of the blockchain by the given number of seconds.
-    pub fn increase_time(&self, seconds: u64) {
+    /// Recorder of the gas of every transaction executed or simulated by this app.
+    /// A fork starts with an empty recorder.
+    pub fn gas_recorder(&self) -> &GasRecorder {
+        &self.gas_recorder
+    }
+
+    /// Increase the time of the blockchain by the given number of seconds,
+    /// returning the result of finalizing the produced block.
+    pub fn increase_time(&self, seconds: u64) -> RunnerResult<BlockResult> {
+        let _lock = self.lock_block();
+        unsafe {
+            let res = IncreaseTime(self.id, seconds.try_into().unwrap());
+            decode_block_result(res)
+        }
+    }
+
+    /// Produce a new block with the given block time,
+    /// which must be after the current block time.
+    pub fn set_block_time(&self, time: Timestamp) -> RunnerResult<BlockResult> {
+        let nanos = i64::try_from(time.nanos()).map_err(|_| {
+            RunnerError::GenericError(format!("block time {} is out of range", time))
+        })?;
+
+        let _lock = self.lock_block();
+        unsafe {
+            let res = SetBlockTime(self.id, nanos);
+            decode_block_result(res)
+        }
+    }
+
+    /// Produce the given number of empty blocks, with the block time
+    /// increased by `seconds_per_block` for each of them.
+    /// The result has the events and validator updates of all produced blocks.
+    pub fn advance_blocks(&self, blocks: u64, seconds_per_block: u64) -> RunnerResult<BlockResult> {
+        let _lock = self.lock_block();
+        unsafe {
+            let res = AdvanceBlocks(self.id, blocks, seconds_per_block);
+            decode_block_result(res)
+        }
+    }
+
+    /// Produce empty blocks until the chain reaches the given height, which must be greater
+    /// than the current height, with the block time increased by `seconds_per_block` for each of them.
+    /// The result has the events and validator updates of all produced blocks.
+    pub fn advance_to_height(
+        &self,
+        height: i64,
+        seconds_per_block: u64,
+    ) -> RunnerResult<BlockResult> {
+        let _lock = self.lock_block();
         unsafe {
-            IncreaseTime(self.id, seconds.try_into().unwrap());
+            let res = AdvanceToHeight(self.id, height, seconds_per_block);
+            decode_block_result(res)
+        }
+    }
+
+    /// Take a snapshot of the current chain state, including block height, block time
+    /// and account sequences. The chain can be rewound to it with [`BaseApp::restore`].
+    pub fn snapshot(&self) -> Snapshot {
+        let _lock = self.lock_block();
+        let id = unsafe { TakeSnapshot(self.id) };
+        Snapshot {
+            id,
+            env_id: self.id,
+        }
+    }
+
+    /// Restore the chain state captured by the given snapshot, which must be taken from
+    /// this app. The same snapshot can be restored any number of times.
+    pub fn restore(&self, snapshot: &Snapshot) -> RunnerResult<()> {
+        if snapshot.env_id != self.id {
+            return Err(RunnerError::GenericError(
+                "snapshot was taken from another app".to_string(),
+            ));
+        }
+
+        let _lock = self.lock_block();
+        unsafe {
+            let res = RestoreSnapshot(self.id, snapshot.id);
+
+            // returns empty bytes if success
+            RawResult::from_non_null_ptr(res).into_result()?;
+            Ok(())
         }
     }
 
@@ -125,6 +339,7 @@ impl BaseApp {
         redefine_as_go_string!(coins_json);
 
         let base64_priv = unsafe {
+            let _lock = self.lock_block();
             BeginBlock(self.id);
             let addr = InitAccount(self.id, coins_json);
             EndBlock(self.id);
@@ -151,6 +366,115 @@ impl BaseApp {
             },
         ))
     }
+
+    /// Initialize account derived from the mnemonic with the given HD path,
+    /// e.g. `m/44'/118'/0'/0/0`, with initial balance of any coins.
+    /// The same mnemonic and path always result in the same address.
+    pub fn init_account_from_mnemonic(
+        &self,
+        mnemonic: &str,
+        hd_path: &str,
+        coins: &[Coin],
+    ) -> RunnerResult<SigningAccount> {
+        let to_decode_error =
+            |e: bip32::Error| DecodeError::SigningKeyDecodeError { msg: e.to_string() };
+
+        let mnemonic = Mnemonic::new(mnemonic, Language::English).map_err(to_decode_error)?;
+        let path = hd_path.parse::<DerivationPath>().map_err(to_decode_error)?;
+        let xprv = XPrv::derive_from_path(mnemonic.to_seed("").as_bytes(), &path)
+            .map_err(to_decode_error)?;
+
+        let signing_key = SigningKey::from_slice(&xprv.private_key().to_bytes()).map_err(|e| {
+            let msg = e.to_string();
+            DecodeError::SigningKeyDecodeError { msg }
+        })?;
+
+        self.init_account_with_signing_key(signing_key, coins)
+    }
+
+    /// Initialize account with the private key derived from the seed,
+    /// with initial balance of any coins.
+    /// The same seed always results in the same address.
+    pub fn init_account_with_seed(
+        &self,
+        seed: &str,
+        coins: &[Coin],
+    ) -> RunnerResult<SigningAccount> {
+        let signing_key =
+            SigningKey::from_slice(&Sha256::digest(seed.as_bytes())).map_err(|e| {
+                let msg = e.to_string();
+                DecodeError::SigningKeyDecodeError { msg }
+            })?;
+
+        self.init_account_with_signing_key(signing_key, coins)
+    }
+
+    fn init_account_with_signing_key(
+        &self,
+        signing_key: SigningKey,
+        coins: &[Coin],
+    ) -> RunnerResult<SigningAccount> {
+        let account = SigningAccount::new(
+            self.address_prefix.clone(),
+            signing_key,
+            FeeSetting::Auto {
+                gas_price: Coin::new(MIN_GAS_PRICE, self.fee_denom.clone()),
+                gas_adjustment: self.default_gas_adjustment,
+            },
+        );
+
+        self.fund_address(&account.address(), coins)?;
+
+        Ok(account)
+    }
+
+    /// Initialize multisig account made of the members with the given threshold,
+    /// with initial balance of any coins.
+    pub fn init_multisig_account(
+        &self,
+        threshold: u32,
+        members: Vec<SigningAccount>,
+        coins: &[Coin],
+    ) -> RunnerResult<MultisigAccount> {
+        let multisig = MultisigAccount::new(
+            self.address_prefix.clone(),
+            threshold,
+            members,
+            FeeSetting::Auto {
+                gas_price: Coin::new(MIN_GAS_PRICE, self.fee_denom.clone()),
+                gas_adjustment: self.default_gas_adjustment,
+            },
+        );
+
+        self.fund_address(&multisig.address(), coins)?;
+
+        Ok(multisig)
+    }
+
+    /// Mint the coins to any bech32 address, including contract and module accounts,
+    /// without going through the ante handler.
+    pub fn fund_address(&self, address: &str, coins: &[Coin]) -> RunnerResult<()> {
+        let mut coins = coins.to_vec();
+
+        // invalid coins if denom are unsorted
+        coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+        let coins_json = serde_json::to_string(&coins).map_err(EncodeError::JsonEncodeError)?;
+        redefine_as_go_string!(coins_json);
+        redefine_as_go_string!(address);
+
+        let _lock = self.lock_block();
+        unsafe {
+            BeginBlock(self.id);
+            let res = FundAccount(self.id, address, coins_json);
+            EndBlock(self.id);
+
+            // returns empty bytes if success
+            RawResult::from_non_null_ptr(res).into_result()?;
+            Ok(())
+        }
+    }
+
     /// Convinience function to create multiple accounts with the same
     /// Initial coins balance
     pub fn init_accounts(&self, coins: &[Coin], count: u64) -> RunnerResult<Vec<SigningAccount>> {
@@ -160,44 +484,210 @@ impl BaseApp {
     fn create_signed_tx<I>(
         &self,
         msgs: I,
-        signer: &SigningAccount,
+        signers: &[TxSigner],
         fee: Fee,
+        options: &TxOptions,
     ) -> RunnerResult<Vec<u8>>
     where
         I: IntoIterator<Item = cosmrs::Any>,
     {
-        let tx_body = tx::Body::new(msgs, "", 0u32);
-        let addr = signer.address();
-        redefine_as_go_string!(addr);
-
-        let seq = unsafe { AccountSequence(self.id, addr) };
-
-        let account_number = unsafe { AccountNumber(self.id, addr) };
-        let signer_info = SignerInfo::single_direct(Some(signer.public_key()), seq);
-        let auth_info = signer_info.auth_info(fee);
-        let sign_doc = tx::SignDoc::new(
-            &tx_body,
-            &auth_info,
-            &(self
-                .chain_id
-                .parse()
-                .expect("parse const str of chain id should never fail")),
-            account_number,
-        )
-        .map_err(|e| match e.downcast::<prost::EncodeError>() {
-            Ok(encode_err) => EncodeError::ProtoEncodeError(encode_err),
-            Err(e) => panic!("expect `prost::EncodeError` but got {:?}", e),
-        })?;
+        let mut tx_body = tx::Body::new(msgs, options.memo.clone(), 0u32);
+        tx_body.timeout_height = options.timeout_height.try_into()?;
+
+        // one signer info for each signer, in the order of the signatures
+        let (signer_data, signer_infos): (Vec<_>, Vec<_>) = signers
+            .iter()
+            .enumerate()
+            .map(|(i, signer)| {
+                let addr = signer.address();
 
-        let tx_raw = sign_doc.sign(signer.signing_key()).unwrap();
+                // overrides apply to the first signer only
+                let (seq, account_number) = match (i, options.sequence, options.account_number) {
+                    (0, Some(seq), Some(account_number)) => (seq, account_number),
+                    (0, Some(seq), None) => (seq, self.account_number(&addr)?),
+                    (0, None, Some(account_number)) => {
+                        (self.account_sequence(&addr)?, account_number)
+                    }
+                    _ => (self.account_sequence(&addr)?, self.account_number(&addr)?),
+                };
+
+                Ok((
+                    (account_number, seq),
+                    signer.signer_info(seq, options.sign_mode.into()),
+                ))
+            })
+            .collect::<RunnerResult<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+
+        let body_bytes = tx_body.into_bytes().map_err(to_encode_error)?;
+        let auth_info_bytes = AuthInfo {
+            signer_infos,
+            fee: Some(fee.into()),
+            ..Default::default()
+        }
+        .encode_to_vec();
 
-        tx_raw
-            .to_bytes()
-            .map_err(|e| match e.downcast::<prost::EncodeError>() {
-                Ok(encode_err) => EncodeError::ProtoEncodeError(encode_err),
-                Err(e) => panic!("expect `prost::EncodeError` but got {:?}", e),
+        // amino JSON sign bytes are produced by the chain from the unsigned transaction
+        let unsigned_tx = TxRaw {
+            body_bytes: body_bytes.clone(),
+            auth_info_bytes: auth_info_bytes.clone(),
+            signatures: vec![vec![]; signers.len()],
+        };
+
+        let signatures = signers
+            .iter()
+            .zip(signer_data)
+            .enumerate()
+            .map(|(signer_index, (signer, (account_number, seq)))| {
+                let sign_bytes = match options.sign_mode {
+                    SignMode::Direct => SignDoc {
+                        body_bytes: body_bytes.clone(),
+                        auth_info_bytes: auth_info_bytes.clone(),
+                        chain_id: self.chain_id.clone(),
+                        account_number,
+                    }
+                    .encode_to_vec(),
+                    SignMode::LegacyAminoJson => self.sign_bytes(
+                        options.sign_mode,
+                        &unsigned_tx,
+                        signer_index,
+                        account_number,
+                        seq,
+                    )?,
+                };
+
+                Ok(signer.sign(&sign_bytes)?)
             })
-            .map_err(RunnerError::EncodeError)
+            .collect::<RunnerResult<Vec<_>>>()?;
+
+        let tx_raw = TxRaw {
+            body_bytes,
+            auth_info_bytes,
+            signatures,
+        };
+
+        Ok(tx_raw.encode_to_vec())
+    }
+
+    /// Sign bytes of the signer at `signer_index` in the given mode, produced by the chain
+    /// with the account number and sequence signed by the signer.
+    fn sign_bytes(
+        &self,
+        sign_mode: SignMode,
+        unsigned_tx: &TxRaw,
+        signer_index: usize,
+        account_number: u64,
+        sequence: u64,
+    ) -> RunnerResult<Vec<u8>> {
+        let base64_tx_bytes = BASE64_STANDARD.encode(unsigned_tx.encode_to_vec());
+        redefine_as_go_string!(base64_tx_bytes);
+
+        unsafe {
+            let res = GetSignBytes(
+                self.id,
+                ProtoSignMode::from(sign_mode) as i32,
+                base64_tx_bytes,
+                signer_index as i64,
+                account_number,
+                sequence,
+            );
+            RawResult::from_non_null_ptr(res).into_result()
+        }
+    }
+
+    /// Sequence of the account at the address in the last committed state.
+    pub fn account_sequence(&self, address: &str) -> RunnerResult<u64> {
+        redefine_as_go_string!(address);
+
+        unsafe {
+            let res = AccountSequence(self.id, address);
+            let res = RawResult::from_non_null_ptr(res).into_result()?;
+            Ok(decode_u64(res))
+        }
+    }
+
+    /// Account number of the account at the address, which must exist.
+    pub fn account_number(&self, address: &str) -> RunnerResult<u64> {
+        redefine_as_go_string!(address);
+
+        unsafe {
+            let res = AccountNumber(self.id, address);
+            let res = RawResult::from_non_null_ptr(res).into_result()?;
+            Ok(decode_u64(res))
+        }
+    }
+
+    /// Build and sign the transaction the same way `execute_*` methods do, without executing it.
+    /// The fee is calculated from the signer fee setting unless overridden by the options.
+    pub fn build_signed_tx(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signer: &SigningAccount,
+        options: &TxOptions,
+    ) -> RunnerResult<Vec<u8>> {
+        let signer = TxSigner::Single(signer);
+
+        self.sign_tx(msgs, &[signer], signer, options)
+    }
+
+    /// Execute the encoded `TxRaw` in a new block, e.g. a transaction signed outside of
+    /// test-tube, a malformed or a replayed one.
+    pub fn execute_tx_bytes<R>(&self, tx_bytes: &[u8]) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        unsafe { self.run_block(|| self.deliver_tx_bytes(tx_bytes)) }
+    }
+
+    /// Run the encoded `TxRaw` through CheckTx, the mempool admission check of a node,
+    /// against the last committed state. Admitted transactions update the check state,
+    /// so the same transaction is rejected until the next block is committed.
+    pub fn check_tx(&self, tx_bytes: &[u8]) -> RunnerResult<CheckTxResult> {
+        let base64_tx_bytes = BASE64_STANDARD.encode(tx_bytes);
+        redefine_as_go_string!(base64_tx_bytes);
+
+        unsafe {
+            let res = CheckTx(self.id, base64_tx_bytes);
+            let res = RawResult::from_non_null_ptr(res).into_result()?;
+
+            Ok(ResponseCheckTx::decode(res.as_slice())
+                .map_err(DecodeError::ProtoDecodeError)?
+                .into())
+        }
+    }
+
+    /// Get the executed transaction with the given hex encoded hash, e.g. the `tx_hash` of
+    /// an [`ExecuteResponse`]. Failed transactions are indexed too, with their error code
+    /// and without events.
+    pub fn query_tx(&self, hash: &str) -> RunnerResult<TxResponse> {
+        redefine_as_go_string!(hash);
+
+        unsafe {
+            let res = QueryTx(self.id, hash);
+            let res = RawResult::from_non_null_ptr(res).into_result()?;
+
+            TxResponse::decode(res.as_slice())
+                .map_err(DecodeError::ProtoDecodeError)
+                .map_err(RunnerError::DecodeError)
+        }
+    }
+
+    /// Search the executed transactions matching the query, in the order they were executed.
+    /// Failed transactions are indexed without events, so they only match `tx.hash` and
+    /// `tx.height` conditions.
+    pub fn search_txs(&self, query: &EventQuery) -> RunnerResult<Vec<TxResponse>> {
+        let query = query.to_string();
+        redefine_as_go_string!(query);
+
+        unsafe {
+            let res = SearchTxs(self.id, query);
+            let res = RawResult::from_non_null_ptr(res).into_result()?;
+
+            Ok(SearchTxsResult::decode(res.as_slice())
+                .map_err(DecodeError::ProtoDecodeError)?
+                .txs)
+        }
     }
 
     pub fn simulate_tx<I>(
@@ -210,8 +700,9 @@ impl BaseApp {
     {
         self.simulate_tx_bytes(&self.create_signed_tx(
             msgs,
-            signer,
+            &[TxSigner::Single(signer)],
             self.default_simulation_fee(),
+            &TxOptions::default(),
         )?)
     }
 
@@ -234,58 +725,232 @@ impl BaseApp {
 
         unsafe {
             let res = Simulate(self.id, base64_tx_bytes);
-            let res = RawResult::from_non_null_ptr(res).into_result()?;
+            let res = RawResult::from_non_null_ptr(res)
+                .into_result()
+                .inspect_err(|err| {
+                    self.gas_recorder
+                        .record_error(tx_bytes, GasRecordKind::Simulate, err)
+                })?;
 
-            cosmrs::proto::cosmos::base::abci::v1beta1::GasInfo::decode(res.as_slice())
-                .map_err(DecodeError::ProtoDecodeError)
-                .map_err(RunnerError::DecodeError)
+            let gas_info =
+                cosmrs::proto::cosmos::base::abci::v1beta1::GasInfo::decode(res.as_slice())
+                    .map_err(DecodeError::ProtoDecodeError)?;
+            self.gas_recorder
+                .record(tx_bytes, GasRecordKind::Simulate, &gas_info);
+
+            Ok(gas_info)
         }
     }
 
-    fn calculate_fee<I>(&self, msgs: I, signer: &SigningAccount) -> RunnerResult<Fee>
-    where
-        I: IntoIterator<Item = cosmrs::Any>,
-    {
-        match &signer.fee_setting() {
+    fn calculate_fee(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signers: &[TxSigner],
+        fee_payer: TxSigner,
+        options: &TxOptions,
+    ) -> RunnerResult<Fee> {
+        let (amount, gas_limit) = match fee_payer.fee_setting() {
             FeeSetting::Auto {
                 gas_price,
                 gas_adjustment,
             } => {
-                let gas_info = self.simulate_tx(msgs, signer)?;
-                let gas_limit = ((gas_info.gas_used as f64) * (gas_adjustment)).ceil() as u64;
-
-                let amount = cosmrs::Coin {
-                    denom: self.fee_denom.parse().unwrap(),
-                    amount: (((gas_limit as f64) * (gas_price.amount.u128() as f64)).ceil() as u64)
-                        .into(),
+                let gas_limit = match options.gas_limit {
+                    Some(gas_limit) => gas_limit,
+                    None => {
+                        // simulation checks the sequence, so it always uses the on chain one
+                        let options = TxOptions {
+                            sequence: None,
+                            account_number: None,
+                            ..options.clone()
+                        };
+                        let fee = self.with_fee_options(self.default_simulation_fee(), &options)?;
+                        let tx = self.create_signed_tx(msgs, signers, fee, &options)?;
+                        let gas_info = self.simulate_tx_bytes(&tx)?;
+                        ((gas_info.gas_used as f64) * (gas_adjustment)).ceil() as u64
+                    }
                 };
 
-                Ok(Fee::from_amount_and_gas(amount, gas_limit))
-            }
-            FeeSetting::Custom { .. } => {
-                panic!("estimate fee is a private function and should never be called when fee_setting is Custom");
+                let amount = options.fee_amount.clone().unwrap_or_else(|| {
+                    Coin::new(
+                        ((gas_limit as f64) * (gas_price.amount.u128() as f64)).ceil() as u128,
+                        self.fee_denom.clone(),
+                    )
+                });
+
+                (amount, gas_limit)
             }
-        }
+            FeeSetting::Custom { amount, gas_limit } => (
+                options.fee_amount.clone().unwrap_or_else(|| amount.clone()),
+                options.gas_limit.unwrap_or(*gas_limit),
+            ),
+        };
+
+        let fee = Fee::from_amount_and_gas(
+            cosmrs::Coin {
+                denom: amount.denom.parse()?,
+                amount: amount.amount.u128(),
+            },
+            gas_limit,
+        );
+
+        self.with_fee_options(fee, options)
+    }
+
+    /// Set fee payer and granter from the options.
+    fn with_fee_options(&self, mut fee: Fee, options: &TxOptions) -> RunnerResult<Fee> {
+        fee.payer = options.fee_payer.as_deref().map(str::parse).transpose()?;
+        fee.granter = options.fee_granter.as_deref().map(str::parse).transpose()?;
+
+        Ok(fee)
     }
 
     /// Ensure that all execution that happens in `execution` happens in a block
     /// and end block properly, no matter it suceeds or fails.
     unsafe fn run_block<T, E>(&self, execution: impl Fn() -> Result<T, E>) -> Result<T, E> {
-        unsafe { BeginBlock(self.id) };
-        match execution() {
-            ok @ Ok(_) => {
-                unsafe { EndBlock(self.id) };
-                ok
-            }
-            err @ Err(_) => {
-                unsafe { EndBlock(self.id) };
-                err
+        let _lock = self.lock_block();
+        BeginBlock(self.id);
+        let result = {
+            let _guard = EndBlockOnPanic(self);
+            execution()
+        };
+        EndBlock(self.id);
+        result
+    }
+
+    /// Sign the transaction by all signers and deliver it in the current block,
+    /// without beginning or ending it.
+    pub(crate) fn deliver_tx<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signers: &[TxSigner],
+        fee_payer: TxSigner,
+        options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        let tx = self.sign_tx(msgs, signers, fee_payer, options)?;
+
+        if options.check_tx {
+            let res = self.check_tx(&tx)?;
+            if !res.is_ok() {
+                return Err(RunnerError::ExecuteError {
+                    msg: res.log,
+                    code: res.code,
+                    codespace: res.codespace,
+                    gas_used: res.gas_used as u64,
+                });
             }
         }
+
+        self.deliver_tx_bytes(&tx)
+    }
+
+    /// Sign the transaction by all signers, with the fee calculated from the fee payer setting.
+    /// The fee payer signs too if it is not one of the signers.
+    fn sign_tx(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signers: &[TxSigner],
+        fee_payer: TxSigner,
+        options: &TxOptions,
+    ) -> RunnerResult<Vec<u8>> {
+        let mut signers = signers.to_vec();
+        if !signers
+            .iter()
+            .any(|signer| signer.address() == fee_payer.address())
+        {
+            signers.push(fee_payer);
+        }
+
+        // the first signer pays the fee unless the payer is set explicitly
+        let options = if options.fee_payer.is_none() && signers[0].address() != fee_payer.address()
+        {
+            options.clone().with_fee_payer(fee_payer.address())
+        } else {
+            options.clone()
+        };
+
+        let fee = self.calculate_fee(msgs.clone(), &signers, fee_payer, &options)?;
+        self.create_signed_tx(msgs, &signers, fee, &options)
+    }
+
+    /// Deliver the encoded transaction in the current block, without beginning or ending it.
+    pub(crate) fn deliver_tx_bytes<R>(&self, tx_bytes: &[u8]) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        let base64_req = BASE64_STANDARD.encode(tx_bytes);
+        redefine_as_go_string!(base64_req);
+
+        let mut res: ExecuteResponse<R> = unsafe {
+            let res = Execute(self.id, base64_req);
+            let res = RawResult::from_non_null_ptr(res)
+                .into_result()
+                .inspect_err(|err| {
+                    self.gas_recorder
+                        .record_error(tx_bytes, GasRecordKind::Execute, err)
+                })?;
+
+            SimulationResponse::decode(res.as_slice())
+                .map_err(DecodeError::ProtoDecodeError)?
+                .try_into()?
+        };
+
+        res.tx_hash = Sha256::digest(tx_bytes)
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect();
+        res.height = self.get_block_height() as u64;
+        res.block_time = Timestamp::from_nanos(self.get_block_time_nanos() as u64);
+        self.gas_recorder
+            .record(tx_bytes, GasRecordKind::Execute, &res.gas_info);
+
+        Ok(res)
+    }
+
+    /// Deliver all transactions executed through the [`Block`] passed to `execution`
+    /// in a single block, then end the block. Returns the output of `execution`
+    /// together with the result of finalizing the block.
+    ///
+    /// Fee simulation runs against the last committed state, so a signer sending
+    /// more than one transaction in the block needs `FeeSetting::Custom`.
+    /// The block is ended even if `execution` panics.
+    ///
+    /// Other threads sharing the app wait for the block to end before producing theirs.
+    /// `execution` must only use the [`Block`], producing a block with the app itself,
+    /// e.g. with [`BaseApp::execute_tx_bytes`], would deadlock.
+    pub fn block<T>(&self, execution: impl FnOnce(&Block) -> T) -> RunnerResult<(T, BlockResult)> {
+        let _lock = self.lock_block();
+        unsafe { BeginBlock(self.id) };
+        let output = {
+            let _guard = EndBlockOnPanic(self);
+            execution(&Block::new(self))
+        };
+        let block_result = self.end_block()?;
+
+        Ok((output, block_result))
+    }
+
+    /// Hold the block lock of the app until the guard is dropped. A panic within a block
+    /// poisons the lock, which is recovered since the block is ended anyway.
+    fn lock_block(&self) -> MutexGuard<'_, ()> {
+        self.block_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// End the current block and commit it, returning the finalize block result.
+    fn end_block(&self) -> RunnerResult<BlockResult> {
+        unsafe {
+            let res = EndBlockWithResult(self.id);
+            decode_block_result(res)
+        }
     }
 
     /// Set parameter set for a given subspace.
     pub fn set_param_set(&self, subspace: &str, pset: impl Into<Any>) -> RunnerResult<()> {
+        let _lock = self.lock_block();
         unsafe {
             BeginBlock(self.id);
             let pset = Message::encode_to_vec(&pset.into());
@@ -319,6 +984,109 @@ impl BaseApp {
     }
 }
 
+pub(crate) fn encode_msgs<M>(msgs: &[(M, &str)]) -> RunnerResult<Vec<cosmrs::Any>>
+where
+    M: ::prost::Message,
+{
+    msgs.iter()
+        .map(|(msg, type_url)| {
+            let mut buf = Vec::new();
+            M::encode(msg, &mut buf).map_err(EncodeError::ProtoEncodeError)?;
+
+            Ok(cosmrs::Any {
+                type_url: type_url.to_string(),
+                value: buf,
+            })
+        })
+        .collect::<Result<Vec<cosmrs::Any>, RunnerError>>()
+}
+
+/// Account signing a transaction built by [`BaseApp`].
+#[derive(Clone, Copy)]
+pub(crate) enum TxSigner<'a> {
+    Single(&'a SigningAccount),
+    Multisig(&'a MultisigAccount),
+}
+
+impl<'a> TxSigner<'a> {
+    fn address(&self) -> String {
+        match self {
+            TxSigner::Single(signer) => signer.address(),
+            TxSigner::Multisig(multisig) => multisig.address(),
+        }
+    }
+
+    fn fee_setting(&self) -> &'a FeeSetting {
+        match self {
+            TxSigner::Single(signer) => signer.fee_setting(),
+            TxSigner::Multisig(multisig) => multisig.fee_setting(),
+        }
+    }
+
+    fn signer_info(&self, sequence: u64, sign_mode: ProtoSignMode) -> ProtoSignerInfo {
+        match self {
+            TxSigner::Single(signer) => ProtoSignerInfo {
+                public_key: Some(signer.public_key().into()),
+                mode_info: Some(ModeInfo {
+                    sum: Some(mode_info::Sum::Single(mode_info::Single {
+                        mode: sign_mode as i32,
+                    })),
+                }),
+                sequence,
+            },
+            TxSigner::Multisig(multisig) => multisig.signer_info(sequence, sign_mode),
+        }
+    }
+
+    fn sign(&self, sign_doc_bytes: &[u8]) -> Result<Vec<u8>, cosmrs::ErrorReport> {
+        match self {
+            TxSigner::Single(signer) => signer.signer().sign(sign_doc_bytes),
+            TxSigner::Multisig(multisig) => multisig.sign(sign_doc_bytes),
+        }
+    }
+}
+
+fn to_encode_error(e: cosmrs::ErrorReport) -> EncodeError {
+    match e.downcast::<prost::EncodeError>() {
+        Ok(encode_err) => EncodeError::ProtoEncodeError(encode_err),
+        Err(e) => panic!("expect `prost::EncodeError` but got {:?}", e),
+    }
+}
+
+/// Integers, e.g. the env id of a newly created environment, are returned as 8 big-endian bytes.
+fn decode_u64(bytes: Vec<u8>) -> u64 {
+    u64::from_be_bytes(
+        bytes
+            .try_into()
+            .expect("integer must be encoded as 8 big-endian bytes"),
+    )
+}
+
+/// Decode the finalize block response returned by the functions producing blocks.
+///
+/// # Safety
+///
+/// `res` must be a non null result pointer returned by the Go library.
+unsafe fn decode_block_result(res: *mut std::os::raw::c_char) -> RunnerResult<BlockResult> {
+    let res = RawResult::from_non_null_ptr(res).into_result()?;
+
+    ResponseFinalizeBlock::decode(res.as_slice())
+        .map_err(DecodeError::ProtoDecodeError)?
+        .try_into()
+}
+
+/// Ends the current block if the execution within it panics,
+/// so that the env is not left in the middle of the block.
+struct EndBlockOnPanic<'a>(&'a BaseApp);
+
+impl Drop for EndBlockOnPanic<'_> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            unsafe { EndBlock(self.0.id) };
+        }
+    }
+}
+
 /// Cleanup the test environment when the app is dropped.
 impl Drop for BaseApp {
     fn drop(&mut self) {
@@ -338,20 +1106,7 @@ impl<'a> Runner<'a> for BaseApp {
         M: ::prost::Message,
         R: ::prost::Message + Default,
     {
-        let msgs = msgs
-            .iter()
-            .map(|(msg, type_url)| {
-                let mut buf = Vec::new();
-                M::encode(msg, &mut buf).map_err(EncodeError::ProtoEncodeError)?;
-
-                Ok(cosmrs::Any {
-                    type_url: type_url.to_string(),
-                    value: buf,
-                })
-            })
-            .collect::<Result<Vec<cosmrs::Any>, RunnerError>>()?;
-
-        self.execute_multiple_raw(msgs, signer)
+        self.execute_multiple_raw(encode_msgs(msgs)?, signer)
     }
 
     fn execute_multiple_raw<R>(
@@ -362,34 +1117,46 @@ impl<'a> Runner<'a> for BaseApp {
     where
         R: ::prost::Message + Default,
     {
-        unsafe {
-            self.run_block(|| {
-                let fee = match &signer.fee_setting() {
-                    FeeSetting::Auto { .. } => self.calculate_fee(msgs.clone(), signer)?,
-                    FeeSetting::Custom { amount, gas_limit } => Fee::from_amount_and_gas(
-                        cosmrs::Coin {
-                            denom: amount.denom.parse().unwrap(),
-                            amount: amount.amount.to_string().parse().unwrap(),
-                        },
-                        *gas_limit,
-                    ),
-                };
-
-                let tx = self.create_signed_tx(msgs.clone(), signer, fee)?;
-
-                let base64_req = BASE64_STANDARD.encode(tx);
-                redefine_as_go_string!(base64_req);
+        self.execute_multiple_raw_with_options(msgs, signer, &TxOptions::default())
+    }
 
-                let res = Execute(self.id, base64_req);
-                let res = RawResult::from_non_null_ptr(res).into_result()?;
+    fn execute_multi_signed_with_options<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signers: &[&SigningAccount],
+        fee_payer: &SigningAccount,
+        options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        let signers = signers
+            .iter()
+            .copied()
+            .map(TxSigner::Single)
+            .collect::<Vec<_>>();
 
-                SimulationResponse::decode(res.as_slice())
-                    .map_err(DecodeError::ProtoDecodeError)?
-                    .try_into()
+        unsafe {
+            self.run_block(|| {
+                self.deliver_tx(msgs.clone(), &signers, TxSigner::Single(fee_payer), options)
             })
         }
     }
 
+    fn execute_multisig_with_options<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        multisig: &MultisigAccount,
+        options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        let signer = TxSigner::Multisig(multisig);
+
+        unsafe { self.run_block(|| self.deliver_tx(msgs.clone(), &[signer], signer, options)) }
+    }
+
     fn query<Q, R>(&self, path: &str, q: &Q) -> RunnerResult<R>
     where
         Q: ::prost::Message,
@@ -0,0 +1,95 @@
+use serde::de::DeserializeOwned;
+
+use crate::account::{MultisigAccount, SigningAccount};
+use crate::runner::app::{encode_msgs, BaseApp, TxSigner};
+use crate::runner::options::TxOptions;
+use crate::runner::result::{RunnerExecuteResult, RunnerResult};
+use crate::runner::Runner;
+
+/// Runner that delivers transactions into the block opened by [`BaseApp::block`],
+/// instead of producing a new block for each of them.
+#[derive(Debug)]
+pub struct Block<'a> {
+    app: &'a BaseApp,
+}
+
+impl<'a> Block<'a> {
+    pub(crate) fn new(app: &'a BaseApp) -> Self {
+        Self { app }
+    }
+
+    /// Execute the encoded `TxRaw` in this block.
+    pub fn execute_tx_bytes<R>(&self, tx_bytes: &[u8]) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        self.app.deliver_tx_bytes(tx_bytes)
+    }
+}
+
+impl<'a> Runner<'a> for Block<'_> {
+    fn execute_multiple<M, R>(
+        &self,
+        msgs: &[(M, &str)],
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<R>
+    where
+        M: ::prost::Message,
+        R: ::prost::Message + Default,
+    {
+        self.execute_multiple_raw(encode_msgs(msgs)?, signer)
+    }
+
+    fn execute_multiple_raw<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        self.execute_multiple_raw_with_options(msgs, signer, &TxOptions::default())
+    }
+
+    fn execute_multi_signed_with_options<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signers: &[&SigningAccount],
+        fee_payer: &SigningAccount,
+        options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        let signers = signers
+            .iter()
+            .copied()
+            .map(TxSigner::Single)
+            .collect::<Vec<_>>();
+
+        self.app
+            .deliver_tx(msgs, &signers, TxSigner::Single(fee_payer), options)
+    }
+
+    fn execute_multisig_with_options<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        multisig: &MultisigAccount,
+        options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        let signer = TxSigner::Multisig(multisig);
+
+        self.app.deliver_tx(msgs, &[signer], signer, options)
+    }
+
+    fn query<Q, R>(&self, path: &str, query: &Q) -> RunnerResult<R>
+    where
+        Q: ::prost::Message,
+        R: ::prost::Message + DeserializeOwned + Default,
+    {
+        self.app.query(path, query)
+    }
+}
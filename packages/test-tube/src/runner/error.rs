@@ -15,8 +15,19 @@ pub enum RunnerError {
     #[error("query error: {}", .msg)]
     QueryError { msg: String },
 
+    /// Failed transaction, `msg` is the raw log. `code` and `codespace` identify the
+    /// registered error, e.g. `5` in `sdk` for insufficient funds.
+    ///
+    /// More fields may be added, match it with `..` and build it with
+    /// [`RunnerError::execute_error`].
     #[error("execute error: {}", .msg)]
-    ExecuteError { msg: String },
+    #[non_exhaustive]
+    ExecuteError {
+        msg: String,
+        code: u32,
+        codespace: String,
+        gas_used: u64,
+    },
 
     #[error("{0}")]
     GenericError(String),
@@ -31,13 +42,45 @@ pub enum RunnerError {
     TendermintRpc(#[from] TendermintRpcError),
 }
 
+/// Codespace and code the SDK reports for errors which are not registered.
+const UNDEFINED_CODESPACE: &str = "undefined";
+const UNDEFINED_CODE: u32 = 1;
+
+impl RunnerError {
+    /// Execute error which does not come from a failed transaction, e.g. an invalid message
+    /// rejected before the transaction is built.
+    pub fn execute_error(msg: impl Into<String>) -> Self {
+        RunnerError::ExecuteError {
+            msg: msg.into(),
+            code: UNDEFINED_CODE,
+            codespace: UNDEFINED_CODESPACE.to_string(),
+            gas_used: 0,
+        }
+    }
+}
+
 impl PartialEq for RunnerError {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (RunnerError::EncodeError(a), RunnerError::EncodeError(b)) => a == b,
             (RunnerError::DecodeError(a), RunnerError::DecodeError(b)) => a == b,
             (RunnerError::QueryError { msg: a }, RunnerError::QueryError { msg: b }) => a == b,
-            (RunnerError::ExecuteError { msg: a }, RunnerError::ExecuteError { msg: b }) => a == b,
+            (
+                RunnerError::ExecuteError {
+                    msg: a,
+                    code: a_code,
+                    codespace: a_codespace,
+                    gas_used: a_gas_used,
+                },
+                RunnerError::ExecuteError {
+                    msg: b,
+                    code: b_code,
+                    codespace: b_codespace,
+                    gas_used: b_gas_used,
+                },
+            ) => {
+                a == b && a_code == b_code && a_codespace == b_codespace && a_gas_used == b_gas_used
+            }
             (RunnerError::ErrorReport(a), RunnerError::ErrorReport(b)) => {
                 a.to_string() == b.to_string()
             }
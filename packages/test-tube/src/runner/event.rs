@@ -0,0 +1,197 @@
+use std::fmt;
+
+use cosmwasm_std::Event;
+
+use crate::runner::error::RunnerError;
+use crate::runner::result::{BlockResult, ExecuteMultiResponse, ExecuteResponse, RunnerResult};
+
+/// Attribute set by wasmd on the events emitted by a contract.
+pub const CONTRACT_ADDRESS_ATTR: &str = "_contract_address";
+
+/// Matcher of an event by its type and attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventMatcher {
+    ty: String,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl EventMatcher {
+    /// Match the events of the given type, e.g. `transfer` or `wasm-custom_event`.
+    pub fn new(ty: impl Into<String>) -> Self {
+        Self {
+            ty: ty.into(),
+            attributes: vec![],
+        }
+    }
+
+    /// Match the `wasm` event of the given contract, with the attributes of its response.
+    pub fn wasm(contract: impl Into<String>) -> Self {
+        Self::new("wasm").with_attr(CONTRACT_ADDRESS_ATTR, contract)
+    }
+
+    /// Require an attribute with the given key and value.
+    pub fn with_attr(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.push((key.into(), Some(value.into())));
+        self
+    }
+
+    /// Require an attribute with the given key and any value.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.attributes.push((key.into(), None));
+        self
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        event.ty == self.ty
+            && self.attributes.iter().all(|(key, value)| {
+                event
+                    .attributes
+                    .iter()
+                    .any(|a| a.key == *key && value.as_ref().is_none_or(|value| a.value == *value))
+            })
+    }
+}
+
+/// Query over the events of the executed transactions, in the grammar of the CometBFT
+/// `tx_search`, e.g. `message.sender='core1...' AND wasm.action='transfer'`.
+/// The hash and height of the transactions are matched by `tx.hash` and `tx.height`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventQuery {
+    conditions: Vec<String>,
+}
+
+impl EventQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the attribute `key`, in the `{event type}.{attribute key}` form, to equal `value`.
+    /// The grammar has no escape for quotes, so values containing `'` are rejected.
+    pub fn with_eq(self, key: &str, value: impl fmt::Display) -> RunnerResult<Self> {
+        let value = value.to_string();
+        if value.contains('\'') {
+            return Err(RunnerError::GenericError(format!(
+                "query value {} of {} must not contain quotes",
+                value, key
+            )));
+        }
+
+        Ok(self.with_condition(format!("{}='{}'", key, value)))
+    }
+
+    /// Require the attribute `key`, in the `{event type}.{attribute key}` form, to be present.
+    pub fn with_exists(self, key: &str) -> Self {
+        self.with_condition(format!("{} EXISTS", key))
+    }
+
+    /// Require the transactions to be executed at the given height.
+    pub fn with_height(self, height: u64) -> Self {
+        self.with_condition(format!("tx.height={}", height))
+    }
+
+    /// Require any condition of the grammar, e.g. `tx.height>5` or `transfer.amount CONTAINS 'ucore'`.
+    pub fn with_condition(mut self, condition: impl Into<String>) -> Self {
+        self.conditions.push(condition.into());
+        self
+    }
+}
+
+impl From<&str> for EventQuery {
+    fn from(query: &str) -> Self {
+        Self::new().with_condition(query)
+    }
+}
+
+impl fmt::Display for EventQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.conditions.join(" AND "))
+    }
+}
+
+/// Queries over the events emitted by a transaction or a block.
+pub trait Events {
+    fn events(&self) -> &[Event];
+
+    /// All events of the given type, in the order they were emitted.
+    fn find_events(&self, ty: &str) -> Vec<&Event> {
+        self.events().iter().filter(|e| e.ty == ty).collect()
+    }
+
+    /// Value of the first attribute with the given key of the events of the given type.
+    fn attr(&self, ty: &str, key: &str) -> Option<&str> {
+        self.events()
+            .iter()
+            .filter(|e| e.ty == ty)
+            .flat_map(|e| e.attributes.iter())
+            .find(|a| a.key == key)
+            .map(|a| a.value.as_str())
+    }
+
+    fn has_event(&self, matcher: &EventMatcher) -> bool {
+        self.events().iter().any(|e| matcher.matches(e))
+    }
+
+    /// All events matched by the matcher.
+    fn matching_events(&self, matcher: &EventMatcher) -> Vec<&Event> {
+        self.events()
+            .iter()
+            .filter(|e| matcher.matches(e))
+            .collect()
+    }
+
+    /// `wasm` and custom `wasm-*` events emitted by the given contract.
+    fn wasm_events(&self, contract: &str) -> Vec<&Event> {
+        self.events()
+            .iter()
+            .filter(|e| e.ty == "wasm" || e.ty.starts_with("wasm-"))
+            .filter(|e| {
+                e.attributes
+                    .iter()
+                    .any(|a| a.key == CONTRACT_ADDRESS_ATTR && a.value == contract)
+            })
+            .collect()
+    }
+
+    /// Value of the first attribute with the given key of the `wasm` and custom `wasm-*`
+    /// events emitted by the given contract.
+    fn wasm_attr(&self, contract: &str, key: &str) -> Option<&str> {
+        self.wasm_events(contract)
+            .into_iter()
+            .flat_map(|e| e.attributes.iter())
+            .find(|a| a.key == key)
+            .map(|a| a.value.as_str())
+    }
+}
+
+impl Events for [Event] {
+    fn events(&self) -> &[Event] {
+        self
+    }
+}
+
+impl Events for Vec<Event> {
+    fn events(&self) -> &[Event] {
+        self
+    }
+}
+
+impl<R> Events for ExecuteResponse<R>
+where
+    R: prost::Message + Default,
+{
+    fn events(&self) -> &[Event] {
+        &self.events
+    }
+}
+
+impl Events for ExecuteMultiResponse {
+    fn events(&self) -> &[Event] {
+        &self.events
+    }
+}
+
+impl Events for BlockResult {
+    fn events(&self) -> &[Event] {
+        &self.events
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Mutex, PoisonError};
+
+use cosmrs::proto::cosmos::base::abci::v1beta1::GasInfo;
+use cosmrs::proto::cosmos::tx::v1beta1::{AuthInfo, TxBody, TxRaw};
+use prost::Message;
+use serde_json::{json, Value};
+
+use crate::runner::error::{DecodeError, RunnerError};
+use crate::runner::result::RunnerResult;
+
+/// How the gas of a record was consumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GasRecordKind {
+    Execute,
+    Simulate,
+}
+
+impl fmt::Display for GasRecordKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GasRecordKind::Execute => write!(f, "execute"),
+            GasRecordKind::Simulate => write!(f, "simulate"),
+        }
+    }
+}
+
+impl GasRecordKind {
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "execute" => Some(GasRecordKind::Execute),
+            "simulate" => Some(GasRecordKind::Simulate),
+            _ => None,
+        }
+    }
+}
+
+/// Gas of an executed or simulated transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasRecord {
+    /// Label set on the recorder when the transaction ran.
+    pub label: Option<String>,
+    /// Type URLs of the messages of the transaction.
+    pub type_urls: Vec<String>,
+    pub kind: GasRecordKind,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+}
+
+/// Recorder of the gas of every transaction executed or simulated by an app, failed ones
+/// included with the gas they used until they failed.
+#[derive(Debug, Default)]
+pub struct GasRecorder {
+    records: Mutex<Vec<GasRecord>>,
+    label: Mutex<Option<String>>,
+}
+
+impl GasRecorder {
+    /// Label the records of the following transactions, e.g. with the contract version or
+    /// the scenario under test, `None` to stop labeling them.
+    ///
+    /// The label belongs to the app, not to the thread: the transactions run by other
+    /// threads sharing the app are labeled too.
+    pub fn set_label(&self, label: Option<&str>) {
+        *self.label.lock().unwrap() = label.map(str::to_string);
+    }
+
+    /// Label the records of the transactions run by `f`, restoring the previous label
+    /// when it returns or panics. As with [`Self::set_label`], the label is per app.
+    pub fn labeled<T>(&self, label: &str, f: impl FnOnce() -> T) -> T {
+        let previous = self.label.lock().unwrap().replace(label.to_string());
+        let _restore = RestoreLabel {
+            label: &self.label,
+            previous,
+        };
+        f()
+    }
+
+    pub fn records(&self) -> Vec<GasRecord> {
+        self.records.lock().unwrap().clone()
+    }
+
+    pub fn clear(&self) {
+        self.records.lock().unwrap().clear();
+    }
+
+    /// Aggregate the records by label, message type URLs and kind.
+    pub fn report(&self) -> GasReport {
+        let mut entries: BTreeMap<GasReportKey, GasReportEntry> = BTreeMap::new();
+        for record in self.records.lock().unwrap().iter() {
+            let key = GasReportKey {
+                label: record.label.clone().unwrap_or_default(),
+                type_urls: record.type_urls.join(","),
+                kind: record.kind,
+            };
+            entries
+                .entry(key.clone())
+                .and_modify(|entry| {
+                    entry.count += 1;
+                    entry.min_gas_used = entry.min_gas_used.min(record.gas_used);
+                    entry.max_gas_used = entry.max_gas_used.max(record.gas_used);
+                    entry.total_gas_used += record.gas_used;
+                    entry.max_gas_wanted = entry.max_gas_wanted.max(record.gas_wanted);
+                })
+                .or_insert(GasReportEntry {
+                    label: key.label,
+                    type_urls: key.type_urls,
+                    kind: key.kind,
+                    count: 1,
+                    min_gas_used: record.gas_used,
+                    max_gas_used: record.gas_used,
+                    total_gas_used: record.gas_used,
+                    max_gas_wanted: record.gas_wanted,
+                });
+        }
+
+        GasReport {
+            entries: entries.into_values().collect(),
+        }
+    }
+
+    /// Record the gas of the transaction, the type URLs are decoded from its `TxRaw` bytes.
+    pub(crate) fn record(&self, tx_bytes: &[u8], kind: GasRecordKind, gas_info: &GasInfo) {
+        let type_urls = TxRaw::decode(tx_bytes)
+            .and_then(|tx| TxBody::decode(tx.body_bytes.as_slice()))
+            .map(|body| body.messages.into_iter().map(|msg| msg.type_url).collect())
+            .unwrap_or_default();
+
+        self.records.lock().unwrap().push(GasRecord {
+            label: self.label.lock().unwrap().clone(),
+            type_urls,
+            kind,
+            gas_wanted: gas_info.gas_wanted,
+            gas_used: gas_info.gas_used,
+        });
+    }
+
+    /// Record the gas of the transaction which failed with the error, the gas wanted is the
+    /// gas limit of its fee. Errors other than [`RunnerError::ExecuteError`] are not recorded.
+    pub(crate) fn record_error(&self, tx_bytes: &[u8], kind: GasRecordKind, err: &RunnerError) {
+        let RunnerError::ExecuteError { gas_used, .. } = err else {
+            return;
+        };
+
+        let gas_wanted = TxRaw::decode(tx_bytes)
+            .and_then(|tx| AuthInfo::decode(tx.auth_info_bytes.as_slice()))
+            .ok()
+            .and_then(|auth_info| auth_info.fee)
+            .map_or(0, |fee| fee.gas_limit);
+
+        self.record(
+            tx_bytes,
+            kind,
+            &GasInfo {
+                gas_wanted,
+                gas_used: *gas_used,
+            },
+        );
+    }
+}
+
+/// Restores the label of the recorder when dropped.
+struct RestoreLabel<'a> {
+    label: &'a Mutex<Option<String>>,
+    previous: Option<String>,
+}
+
+impl Drop for RestoreLabel<'_> {
+    fn drop(&mut self) {
+        *self.label.lock().unwrap_or_else(PoisonError::into_inner) = self.previous.take();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct GasReportKey {
+    label: String,
+    type_urls: String,
+    kind: GasRecordKind,
+}
+
+/// Gas of the transactions with the same label, message type URLs and kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasReportEntry {
+    /// Label of the records, empty if they are not labeled.
+    pub label: String,
+    /// Comma separated type URLs of the messages.
+    pub type_urls: String,
+    pub kind: GasRecordKind,
+    pub count: u64,
+    pub min_gas_used: u64,
+    pub max_gas_used: u64,
+    pub total_gas_used: u64,
+    pub max_gas_wanted: u64,
+}
+
+impl GasReportEntry {
+    pub fn avg_gas_used(&self) -> u64 {
+        self.total_gas_used / self.count.max(1)
+    }
+
+    fn key(&self) -> GasReportKey {
+        GasReportKey {
+            label: self.label.clone(),
+            type_urls: self.type_urls.clone(),
+            kind: self.kind,
+        }
+    }
+}
+
+/// Entry whose gas grew beyond the threshold compared to the baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasRegression {
+    pub entry: GasReportEntry,
+    pub baseline_max_gas_used: u64,
+}
+
+impl fmt::Display for GasRegression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] {}: max gas used {} > baseline {}",
+            self.entry.kind,
+            self.entry.label,
+            self.entry.type_urls,
+            self.entry.max_gas_used,
+            self.baseline_max_gas_used
+        )
+    }
+}
+
+/// Report of the gas recorded by a [`GasRecorder`], which can be exported
+/// and checked in as the baseline of the following runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasReport {
+    pub entries: Vec<GasReportEntry>,
+}
+
+const CSV_HEADER: &str =
+    "label,type_urls,kind,count,min_gas_used,max_gas_used,avg_gas_used,max_gas_wanted";
+
+impl GasReport {
+    /// Export the report as a JSON array, one object per entry.
+    pub fn to_json(&self) -> String {
+        let entries = Value::Array(
+            self.entries
+                .iter()
+                .map(|entry| {
+                    json!({
+                        "label": entry.label,
+                        "type_urls": entry.type_urls,
+                        "kind": entry.kind.to_string(),
+                        "count": entry.count,
+                        "min_gas_used": entry.min_gas_used,
+                        "max_gas_used": entry.max_gas_used,
+                        "total_gas_used": entry.total_gas_used,
+                        "max_gas_wanted": entry.max_gas_wanted,
+                    })
+                })
+                .collect(),
+        );
+        format!("{:#}", entries)
+    }
+
+    /// Decode a report exported with [`GasReport::to_json`], e.g. a checked-in baseline.
+    pub fn from_json(json: &str) -> RunnerResult<Self> {
+        let value: Value = serde_json::from_str(json).map_err(DecodeError::JsonDecodeError)?;
+        let invalid = || RunnerError::GenericError("invalid gas report".to_string());
+
+        let entries = value
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|entry| {
+                let str_field = |key: &str| entry[key].as_str().map(str::to_string);
+                let u64_field = |key: &str| entry[key].as_u64();
+                let decode = || {
+                    Some(GasReportEntry {
+                        label: str_field("label")?,
+                        type_urls: str_field("type_urls")?,
+                        kind: GasRecordKind::parse(entry["kind"].as_str()?)?,
+                        count: u64_field("count")?,
+                        min_gas_used: u64_field("min_gas_used")?,
+                        max_gas_used: u64_field("max_gas_used")?,
+                        total_gas_used: u64_field("total_gas_used")?,
+                        max_gas_wanted: u64_field("max_gas_wanted")?,
+                    })
+                };
+
+                decode().ok_or_else(invalid)
+            })
+            .collect::<RunnerResult<Vec<_>>>()?;
+
+        Ok(Self { entries })
+    }
+
+    /// Export the report as CSV, one line per entry, e.g. to compare it in a spreadsheet.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{}\n", CSV_HEADER);
+        for entry in &self.entries {
+            csv.push_str(&format!(
+                "\"{}\",\"{}\",{},{},{},{},{},{}\n",
+                entry.label,
+                entry.type_urls,
+                entry.kind,
+                entry.count,
+                entry.min_gas_used,
+                entry.max_gas_used,
+                entry.avg_gas_used(),
+                entry.max_gas_wanted
+            ));
+        }
+        csv
+    }
+
+    /// Entries whose max gas used grew by more than `threshold`, e.g. `0.05` for 5%,
+    /// compared to the same entry of the baseline. Entries missing in the baseline are ignored.
+    pub fn regressions(&self, baseline: &GasReport, threshold: f64) -> Vec<GasRegression> {
+        let baseline: BTreeMap<GasReportKey, &GasReportEntry> = baseline
+            .entries
+            .iter()
+            .map(|entry| (entry.key(), entry))
+            .collect();
+
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let baseline_entry = baseline.get(&entry.key())?;
+                let limit = (baseline_entry.max_gas_used as f64) * (1.0 + threshold);
+                ((entry.max_gas_used as f64) > limit).then(|| GasRegression {
+                    entry: entry.clone(),
+                    baseline_max_gas_used: baseline_entry.max_gas_used,
+                })
+            })
+            .collect()
+    }
+
+    /// Fail with the list of regressions if the gas grew beyond the threshold
+    /// compared to the baseline.
+    pub fn check_baseline(&self, baseline: &GasReport, threshold: f64) -> RunnerResult<()> {
+        let regressions = self.regressions(baseline, threshold);
+        if regressions.is_empty() {
+            return Ok(());
+        }
+
+        Err(RunnerError::GenericError(format!(
+            "gas regressions beyond {}% of the baseline:\n{}",
+            threshold * 100.0,
+            regressions
+                .iter()
+                .map(|regression| regression.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        )))
+    }
+}
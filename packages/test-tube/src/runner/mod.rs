@@ -1,14 +1,21 @@
 use cosmwasm_std::{AnyMsg, CosmosMsg};
 use serde::de::DeserializeOwned;
 
-use crate::account::SigningAccount;
-use crate::runner::result::{RunnerExecuteResult, RunnerResult};
+use crate::account::{MultisigAccount, SigningAccount};
+use crate::runner::app::encode_msgs;
+use crate::runner::options::TxOptions;
+use crate::runner::result::{AnyResponse, ExecuteMultiResponse, RunnerExecuteResult, RunnerResult};
 use crate::utils::{bank_msg_to_any, wasm_msg_to_any};
 use crate::RunnerError;
 
 pub mod app;
+pub mod block;
 pub mod error;
+pub mod event;
+pub mod gas;
+pub mod options;
 pub mod result;
+pub mod snapshot;
 
 pub trait Runner<'a> {
     fn execute<M, R>(
@@ -41,6 +48,125 @@ pub trait Runner<'a> {
     where
         R: ::prost::Message + Default;
 
+    /// Execute messages in one transaction, returning the responses of all of them.
+    fn execute_multiple_all<M>(
+        &self,
+        msgs: &[(M, &str)],
+        signer: &SigningAccount,
+    ) -> RunnerResult<ExecuteMultiResponse>
+    where
+        M: ::prost::Message,
+    {
+        self.execute_multiple_raw_all(encode_msgs(msgs)?, signer)
+    }
+
+    fn execute_multiple_raw_all(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signer: &SigningAccount,
+    ) -> RunnerResult<ExecuteMultiResponse> {
+        self.execute_multiple_raw::<AnyResponse>(msgs, signer)
+            .map(ExecuteMultiResponse::from)
+    }
+
+    fn execute_with_options<M, R>(
+        &self,
+        msg: M,
+        type_url: &str,
+        signer: &SigningAccount,
+        options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        M: ::prost::Message,
+        R: ::prost::Message + Default,
+    {
+        self.execute_multiple_with_options(&[(msg, type_url)], signer, options)
+    }
+
+    fn execute_multiple_with_options<M, R>(
+        &self,
+        msgs: &[(M, &str)],
+        signer: &SigningAccount,
+        options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        M: ::prost::Message,
+        R: ::prost::Message + Default,
+    {
+        self.execute_multiple_raw_with_options(encode_msgs(msgs)?, signer, options)
+    }
+
+    fn execute_multiple_raw_with_options<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signer: &SigningAccount,
+        options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        self.execute_multi_signed_with_options(msgs, &[signer], signer, options)
+    }
+
+    /// Execute messages in one transaction signed by all `signers`, in the given order,
+    /// with the fee paid by `fee_payer`. The fee payer signs the transaction last if it
+    /// is not one of the signers.
+    fn execute_multi_signed<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signers: &[&SigningAccount],
+        fee_payer: &SigningAccount,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        self.execute_multi_signed_with_options(msgs, signers, fee_payer, &TxOptions::default())
+    }
+
+    /// Not supported unless implemented by the runner, e.g. by [`app::BaseApp`].
+    fn execute_multi_signed_with_options<R>(
+        &self,
+        _msgs: Vec<cosmrs::Any>,
+        _signers: &[&SigningAccount],
+        _fee_payer: &SigningAccount,
+        _options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        Err(RunnerError::GenericError(
+            "multi signed transactions are not supported by this runner".to_string(),
+        ))
+    }
+
+    /// Execute messages in one transaction signed by the signing members of the multisig,
+    /// which also pays the fee.
+    fn execute_multisig<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        multisig: &MultisigAccount,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        self.execute_multisig_with_options(msgs, multisig, &TxOptions::default())
+    }
+
+    /// Not supported unless implemented by the runner, e.g. by [`app::BaseApp`].
+    fn execute_multisig_with_options<R>(
+        &self,
+        _msgs: Vec<cosmrs::Any>,
+        _multisig: &MultisigAccount,
+        _options: &TxOptions,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        Err(RunnerError::GenericError(
+            "multisig transactions are not supported by this runner".to_string(),
+        ))
+    }
+
     fn execute_cosmos_msgs<S>(
         &self,
         msgs: &[CosmosMsg],
@@ -0,0 +1,118 @@
+use cosmrs::proto::cosmos::tx::signing::v1beta1::SignMode as ProtoSignMode;
+use cosmwasm_std::Coin;
+
+/// Mode used by the signers to sign the transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SignMode {
+    #[default]
+    Direct,
+    /// Sign the amino JSON representation of the transaction, as Ledger and amino wallets do.
+    /// The sign bytes are produced by the chain, so messages without amino registration fail.
+    LegacyAminoJson,
+}
+
+impl From<SignMode> for ProtoSignMode {
+    fn from(sign_mode: SignMode) -> Self {
+        match sign_mode {
+            SignMode::Direct => ProtoSignMode::Direct,
+            SignMode::LegacyAminoJson => ProtoSignMode::LegacyAminoJson,
+        }
+    }
+}
+
+/// Per transaction options, overriding the defaults used when building the transaction.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TxOptions {
+    /// Memo attached to the transaction body.
+    pub memo: String,
+    /// Block height after which the transaction is no longer valid, `0` means no timeout.
+    pub timeout_height: u64,
+    /// Gas limit to use instead of the one from the signer fee setting.
+    pub gas_limit: Option<u64>,
+    /// Fee amount to use instead of the one from the signer fee setting.
+    pub fee_amount: Option<Coin>,
+    /// Address paying the fee. It must be one of the transaction signers.
+    pub fee_payer: Option<String>,
+    /// Address granting the fee allowance to the fee payer.
+    pub fee_granter: Option<String>,
+    /// Mode used by all signers of the transaction.
+    pub sign_mode: SignMode,
+    /// Run the transaction through CheckTx before delivering it, so it is rejected
+    /// the same way a node rejects a broadcast transaction.
+    pub check_tx: bool,
+    /// Sequence of the first signer to sign with instead of the one on chain,
+    /// e.g. to pre-sign transactions or replay a used sequence.
+    pub sequence: Option<u64>,
+    /// Account number of the first signer to sign with instead of the one on chain.
+    pub account_number: Option<u64>,
+}
+
+impl TxOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_memo(self, memo: impl Into<String>) -> Self {
+        Self {
+            memo: memo.into(),
+            ..self
+        }
+    }
+
+    pub fn with_timeout_height(self, timeout_height: u64) -> Self {
+        Self {
+            timeout_height,
+            ..self
+        }
+    }
+
+    pub fn with_gas_limit(self, gas_limit: u64) -> Self {
+        Self {
+            gas_limit: Some(gas_limit),
+            ..self
+        }
+    }
+
+    pub fn with_fee_amount(self, fee_amount: Coin) -> Self {
+        Self {
+            fee_amount: Some(fee_amount),
+            ..self
+        }
+    }
+
+    pub fn with_fee_payer(self, fee_payer: impl Into<String>) -> Self {
+        Self {
+            fee_payer: Some(fee_payer.into()),
+            ..self
+        }
+    }
+
+    pub fn with_fee_granter(self, fee_granter: impl Into<String>) -> Self {
+        Self {
+            fee_granter: Some(fee_granter.into()),
+            ..self
+        }
+    }
+
+    pub fn with_sign_mode(self, sign_mode: SignMode) -> Self {
+        Self { sign_mode, ..self }
+    }
+
+    pub fn with_check_tx(self, check_tx: bool) -> Self {
+        Self { check_tx, ..self }
+    }
+
+    pub fn with_sequence(self, sequence: u64) -> Self {
+        Self {
+            sequence: Some(sequence),
+            ..self
+        }
+    }
+
+    pub fn with_account_number(self, account_number: u64) -> Self {
+        Self {
+            account_number: Some(account_number),
+            ..self
+        }
+    }
+}
@@ -3,8 +3,11 @@ use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
 use cosmrs::proto::cosmos::base::abci::v1beta1::SimulationResponse;
 use cosmrs::proto::cosmos::base::abci::v1beta1::{GasInfo, TxMsgData};
+use cosmrs::proto::tendermint::v0_38::abci::{
+    ResponseCheckTx, ResponseFinalizeBlock, ValidatorUpdate,
+};
 use cosmrs::rpc::endpoint::broadcast::tx_commit::Response as TxCommitResponse;
-use cosmwasm_std::{Attribute, Event};
+use cosmwasm_std::{Attribute, Coin, Event, Timestamp};
 use prost::Message;
 use std::ffi::CString;
 use std::str::Utf8Error;
@@ -19,8 +22,19 @@ where
 {
     pub data: R,
     pub raw_data: Vec<u8>,
+    /// Responses of all messages of the transaction, `data` is the first one decoded.
+    pub msg_responses: Vec<cosmrs::Any>,
     pub events: Vec<Event>,
     pub gas_info: GasInfo,
+    /// Upper case hex encoded SHA-256 hash of the transaction bytes.
+    pub tx_hash: String,
+    pub height: u64,
+    pub block_time: Timestamp,
+    pub code: u32,
+    pub codespace: String,
+    pub log: String,
+    /// Fee deducted from the fee payer.
+    pub fee: Vec<Coin>,
 }
 
 impl<R> TryFrom<SimulationResponse> for ExecuteResponse<R>
@@ -36,7 +50,7 @@ where
             // since this tx contains exactly 1 msg
             // when getting none of them, that means error
             .first()
-            .ok_or(RunnerError::ExecuteError { msg: result.log })?;
+            .ok_or_else(|| RunnerError::execute_error(result.log.clone()))?;
         let data = R::decode(msg_data.value.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
 
         let events = result
@@ -64,11 +78,20 @@ where
             data,
             #[allow(deprecated)]
             raw_data: result.clone().data,
+            msg_responses: result.msg_responses,
+            fee: fee_from_events(&events),
             events,
             gas_info: GasInfo {
                 gas_wanted: res.clone().gas_info.unwrap().gas_wanted as u64,
                 gas_used: res.gas_info.unwrap().gas_used as u64,
             },
+            // the tx hash and block are known to the runner delivering the transaction only
+            tx_hash: String::new(),
+            height: 0,
+            block_time: Timestamp::default(),
+            code: 0,
+            codespace: String::new(),
+            log: result.log,
         })
     }
 }
@@ -89,8 +112,11 @@ where
             // since this tx contains exactly 1 msg
             // when getting none of them, that means error
             .first()
-            .ok_or(RunnerError::ExecuteError {
+            .ok_or_else(|| RunnerError::ExecuteError {
                 msg: res.log.to_string(),
+                code: res.code.value(),
+                codespace: res.codespace.clone(),
+                gas_used: res.gas_used as u64,
             })?;
 
         let data = R::decode(msg_data.value.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
@@ -116,15 +142,171 @@ where
         Ok(Self {
             data,
             raw_data: res.data.into(),
+            msg_responses: tx_msg_data.msg_responses,
+            fee: fee_from_events(&events),
             events,
             gas_info: GasInfo {
                 gas_wanted: res.gas_wanted as u64,
                 gas_used: res.gas_used as u64,
             },
+            tx_hash: tx_commit_response.hash.to_string(),
+            height: tx_commit_response.height.value(),
+            // block time is not part of the broadcast response
+            block_time: Timestamp::default(),
+            code: res.code.value(),
+            codespace: res.codespace,
+            log: res.log,
+        })
+    }
+}
+
+/// Response of a transaction with many messages, with the responses of all messages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecuteMultiResponse {
+    pub msg_responses: Vec<cosmrs::Any>,
+    pub raw_data: Vec<u8>,
+    pub events: Vec<Event>,
+    pub gas_info: GasInfo,
+    pub tx_hash: String,
+    pub height: u64,
+    pub block_time: Timestamp,
+    pub code: u32,
+    pub codespace: String,
+    pub log: String,
+    pub fee: Vec<Coin>,
+}
+
+impl ExecuteMultiResponse {
+    /// Decode the response of the `i`-th message.
+    pub fn decode<T>(&self, i: usize) -> RunnerResult<T>
+    where
+        T: prost::Message + Default,
+    {
+        let msg_response = self.msg_responses.get(i).ok_or_else(|| {
+            RunnerError::GenericError(format!(
+                "no response for message {}, the transaction has {} responses",
+                i,
+                self.msg_responses.len()
+            ))
+        })?;
+
+        T::decode(msg_response.value.as_slice())
+            .map_err(DecodeError::ProtoDecodeError)
+            .map_err(RunnerError::DecodeError)
+    }
+
+    /// Decode the responses of all messages, which must be of the same type.
+    pub fn decode_all<T>(&self) -> RunnerResult<Vec<T>>
+    where
+        T: prost::Message + Default,
+    {
+        (0..self.msg_responses.len())
+            .map(|i| self.decode(i))
+            .collect()
+    }
+}
+
+impl<R> From<ExecuteResponse<R>> for ExecuteMultiResponse
+where
+    R: prost::Message + Default,
+{
+    fn from(res: ExecuteResponse<R>) -> Self {
+        Self {
+            msg_responses: res.msg_responses,
+            raw_data: res.raw_data,
+            events: res.events,
+            gas_info: res.gas_info,
+            tx_hash: res.tx_hash,
+            height: res.height,
+            block_time: res.block_time,
+            code: res.code,
+            codespace: res.codespace,
+            log: res.log,
+            fee: res.fee,
+        }
+    }
+}
+
+/// Placeholder response type when the responses are decoded later from [`ExecuteMultiResponse`],
+/// decoding any message response succeeds since unknown fields are skipped.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub(crate) struct AnyResponse {}
+
+/// Fee deducted by the ante handler, from the `fee` attribute of the `tx` event, e.g. `100ucore`.
+fn fee_from_events(events: &[Event]) -> Vec<Coin> {
+    events
+        .iter()
+        .filter(|e| e.ty == "tx")
+        .flat_map(|e| e.attributes.iter())
+        .filter(|a| a.key == "fee")
+        .flat_map(|a| a.value.split(','))
+        .filter_map(|coin| {
+            let denom_start = coin.find(|c: char| !c.is_ascii_digit())?;
+            let (amount, denom) = coin.split_at(denom_start);
+            Some(Coin::new(amount.parse::<u128>().ok()?, denom))
+        })
+        .collect()
+}
+
+/// Result of finalizing a block, with the events emitted by the begin and end blockers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockResult {
+    pub events: Vec<Event>,
+    /// Changes of the validator set voting power, applied by the consensus.
+    pub validator_updates: Vec<ValidatorUpdate>,
+}
+
+impl TryFrom<ResponseFinalizeBlock> for BlockResult {
+    type Error = RunnerError;
+
+    fn try_from(res: ResponseFinalizeBlock) -> Result<Self, Self::Error> {
+        let events = res
+            .events
+            .into_iter()
+            .map(|e| {
+                Event::new(e.r#type).add_attributes(e.attributes.into_iter().map(|a| Attribute {
+                    key: a.key,
+                    value: a.value,
+                }))
+            })
+            .collect();
+
+        Ok(Self {
+            events,
+            validator_updates: res.validator_updates,
         })
     }
 }
 
+/// Result of the mempool admission check of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckTxResult {
+    pub code: u32,
+    pub codespace: String,
+    pub log: String,
+    pub gas_wanted: i64,
+    pub gas_used: i64,
+}
+
+impl CheckTxResult {
+    /// Whether the transaction is admitted to the mempool.
+    pub fn is_ok(&self) -> bool {
+        self.code == 0
+    }
+}
+
+impl From<ResponseCheckTx> for CheckTxResult {
+    fn from(res: ResponseCheckTx) -> Self {
+        Self {
+            code: res.code,
+            codespace: res.codespace,
+            log: res.log,
+            gas_wanted: res.gas_wanted,
+            gas_used: res.gas_used,
+        }
+    }
+}
+
 /// `RawResult` facilitates type conversions between Go and Rust,
 ///
 /// Since Go struct could not be exposed via cgo due to limitations on
@@ -143,6 +325,7 @@ where
 ///   0 -> Ok
 ///   1 -> QueryError
 ///   2 -> ExecuteError
+///   3 -> ExecuteError of a failed transaction, JSON encoded with its ABCI code and codespace
 ///
 /// The rest are undefined and remaining spaces are reserved for future use.
 #[derive(Debug)]
@@ -179,9 +362,8 @@ impl RawResult {
                 1 => RunnerError::QueryError {
                     msg: content_string,
                 },
-                2 => RunnerError::ExecuteError {
-                    msg: content_string,
-                },
+                2 => RunnerError::execute_error(content_string),
+                3 => tx_error_from_json(&content_string),
                 _ => panic!("undefined code: {}", code),
             };
             Some(Self(Err(error)))
@@ -202,3 +384,19 @@ impl RawResult {
         self.0
     }
 }
+
+/// Decode the `{"code", "codespace", "log", "gas_used"}` JSON of a failed transaction,
+/// a `DecodeError` if it is not valid JSON.
+fn tx_error_from_json(json: &str) -> RunnerError {
+    let value: serde_json::Value = match serde_json::from_str(json) {
+        Ok(value) => value,
+        Err(err) => return DecodeError::JsonDecodeError(err).into(),
+    };
+
+    RunnerError::ExecuteError {
+        msg: value["log"].as_str().unwrap_or_default().to_string(),
+        code: value["code"].as_u64().unwrap_or_default() as u32,
+        codespace: value["codespace"].as_str().unwrap_or_default().to_string(),
+        gas_used: value["gas_used"].as_u64().unwrap_or_default(),
+    }
+}
@@ -0,0 +1,21 @@
+use crate::bindings::ReleaseSnapshot;
+
+/// Opaque handle to a point-in-time copy of the chain state,
+/// created by [`BaseApp::snapshot`](crate::BaseApp::snapshot).
+///
+/// It can only be restored to the app it was taken from. The underlying state
+/// is released when the handle is dropped.
+#[derive(Debug)]
+pub struct Snapshot {
+    pub(crate) id: u64,
+    /// Id of the env of the app the snapshot was taken from.
+    pub(crate) env_id: u64,
+}
+
+impl Drop for Snapshot {
+    fn drop(&mut self) {
+        unsafe {
+            ReleaseSnapshot(self.id);
+        }
+    }
+}
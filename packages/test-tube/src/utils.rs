@@ -124,8 +124,6 @@ pub fn wasm_msg_to_any(msg: &WasmMsg, signer: &SigningAccount) -> Result<cosmrs:
                 sender: signer.address(),
             },
         ),
-        _ => Err(RunnerError::ExecuteError {
-            msg: "Unsupported WasmMsg".to_string(),
-        }),
+        _ => Err(RunnerError::execute_error("Unsupported WasmMsg")),
     }
 }